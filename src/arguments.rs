@@ -0,0 +1,13 @@
+// The command line: flags like --shuffle stand on their own, options like --seed 7 take the argument after them.
+
+// the argument after the option, None if it isn't given or nothing comes after it
+pub fn value_of(name: &str) -> Option<String> {
+    let arguments: Vec<String> = std::env::args().collect();
+    arguments.iter().position(|argument| argument == name)
+        .and_then(|index| arguments.get(index + 1))
+        .cloned()
+}
+
+pub fn has(flag: &str) -> bool {
+    std::env::args().any(|argument| argument == flag)
+}
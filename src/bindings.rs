@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use sfml::window::joystick::Axis;
+use sfml::window::Key;
+
+use crate::config;
+
+pub const CONFIG_PATH: &str = "mr_binaer.cfg";
+
+// Everything the player can trigger with a key or a joystick
+// on the keyboard digits and letters come in as text (see GameTasks::TextEntered),
+// so they work the same on every keyboard layout, InputOne and InputZero are meant for joysticks
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Restart,
+    Quit,
+    OpenSettings,
+    Pause,
+    CursorLeft,
+    CursorRight,
+    InputOne,
+    InputZero,
+    Submit,
+    DeleteBackward,
+    DeleteForward,
+    Hint,
+    // moving through menus and leaving them
+    MenuUp,
+    MenuDown,
+    Back,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::Restart, Action::Quit, Action::OpenSettings, Action::Pause,
+        Action::CursorLeft, Action::CursorRight, Action::InputOne, Action::InputZero,
+        Action::Submit, Action::DeleteBackward, Action::DeleteForward, Action::Hint,
+        Action::MenuUp, Action::MenuDown, Action::Back,
+    ];
+
+    // the name used in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Restart => "restart",
+            Action::Quit => "quit",
+            Action::OpenSettings => "settings",
+            Action::Pause => "pause",
+            Action::CursorLeft => "cursor_left",
+            Action::CursorRight => "cursor_right",
+            Action::InputOne => "input_one",
+            Action::InputZero => "input_zero",
+            Action::Submit => "submit",
+            Action::DeleteBackward => "delete_backward",
+            Action::DeleteForward => "delete_forward",
+            Action::Hint => "hint",
+            Action::MenuUp => "menu_up",
+            Action::MenuDown => "menu_down",
+            Action::Back => "back",
+        }
+    }
+}
+
+// a key together with the modifiers, that have to be held down
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct KeyChord {
+    pub key: Key,
+    pub alt: bool,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub system: bool,
+}
+
+impl KeyChord {
+    pub fn new(key: Key, alt: bool, ctrl: bool, shift: bool, system: bool) -> KeyChord {
+        KeyChord { key, alt, ctrl, shift, system }
+    }
+
+    pub fn plain(key: Key) -> KeyChord {
+        KeyChord::new(key, false, false, false, false)
+    }
+
+    // pressing only a modifier is not a chord on its own
+    pub fn is_modifier_only(self) -> bool {
+        matches!(self.key, Key::LCONTROL | Key::RCONTROL | Key::LSHIFT | Key::RSHIFT
+            | Key::LALT | Key::RALT | Key::LSYSTEM | Key::RSYSTEM)
+    }
+
+    // parses things like "Q", "Ctrl+Q" or "Ctrl+Shift+R"
+    pub fn parse(text: &str) -> Option<KeyChord> {
+        let mut chord = KeyChord::plain(Key::UNKNOWN);
+        let mut parts: Vec<&str> = text.split('+').map(|part| part.trim()).collect();
+        let key_name = parts.pop()?;
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "alt" => chord.alt = true,
+                "ctrl" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "system" => chord.system = true,
+                _ => return None,
+            }
+        }
+        chord.key = key_from_name(key_name)?;
+        Some(chord)
+    }
+
+    pub fn to_text(self) -> String {
+        let mut text = String::new();
+        if self.ctrl { text.push_str("Ctrl+"); }
+        if self.alt { text.push_str("Alt+"); }
+        if self.shift { text.push_str("Shift+"); }
+        if self.system { text.push_str("System+"); }
+        text.push_str(key_name(self.key).unwrap_or("Unknown"));
+        text
+    }
+}
+
+// a joystick button, or an axis pushed far enough into one direction
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PadInput {
+    Button(u32),
+    AxisPositive(Axis),
+    AxisNegative(Axis),
+}
+
+impl PadInput {
+    // parses things like "Button0", "PovX+" or "Y-"
+    pub fn parse(text: &str) -> Option<PadInput> {
+        let text = text.trim();
+        if let Some(button) = text.strip_prefix("Button") {
+            return button.parse::<u32>().ok().map(PadInput::Button);
+        }
+        if let Some(axis) = text.strip_suffix('+') {
+            return axis_from_name(axis).map(PadInput::AxisPositive);
+        }
+        if let Some(axis) = text.strip_suffix('-') {
+            return axis_from_name(axis).map(PadInput::AxisNegative);
+        }
+        None
+    }
+
+    pub fn to_text(self) -> String {
+        match self {
+            PadInput::Button(button) => format!("Button{}", button),
+            PadInput::AxisPositive(axis) => format!("{}+", axis_name(axis)),
+            PadInput::AxisNegative(axis) => format!("{}-", axis_name(axis)),
+        }
+    }
+}
+
+pub struct Bindings {
+    chords: HashMap<Action, Vec<KeyChord>>,
+    pad_inputs: HashMap<Action, Vec<PadInput>>,
+}
+
+impl Bindings {
+    pub fn action_for(&self, chord: &KeyChord) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| self.chords_of(*action).contains(chord))
+    }
+
+    pub fn action_for_pad(&self, pad_input: &PadInput) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| self.pad_inputs_of(*action).contains(pad_input))
+    }
+
+    pub fn chords_of(&self, action: Action) -> &[KeyChord] {
+        match self.chords.get(&action) {
+            Some(chords) => chords,
+            None => &[],
+        }
+    }
+
+    pub fn pad_inputs_of(&self, action: Action) -> &[PadInput] {
+        match self.pad_inputs.get(&action) {
+            Some(pad_inputs) => pad_inputs,
+            None => &[],
+        }
+    }
+
+    // a chord can only trigger one action, so it gets removed everywhere else first
+    pub fn add(&mut self, action: Action, chord: KeyChord) {
+        for chords in self.chords.values_mut() {
+            chords.retain(|bound| *bound != chord);
+        }
+        self.chords.entry(action).or_default().push(chord);
+    }
+
+    pub fn add_pad(&mut self, action: Action, pad_input: PadInput) {
+        for pad_inputs in self.pad_inputs.values_mut() {
+            pad_inputs.retain(|bound| *bound != pad_input);
+        }
+        self.pad_inputs.entry(action).or_default().push(pad_input);
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.chords.remove(&action);
+        self.pad_inputs.remove(&action);
+    }
+
+    // writes every binding into the config file, other settings in there are left alone
+    pub fn save(&self) {
+        let mut config = config::load(CONFIG_PATH);
+        for action in Action::ALL {
+            let chords: Vec<String> = self.chords_of(action).iter().map(|chord| chord.to_text()).collect();
+            config.set(&format!("bind.{}", action.name()), &chords.join(", "));
+            let pad_inputs: Vec<String> = self.pad_inputs_of(action).iter().map(|pad_input| pad_input.to_text()).collect();
+            config.set(&format!("pad.{}", action.name()), &pad_inputs.join(", "));
+        }
+        if let Err(error) = config.save() {
+            println!("Could not save key bindings: {}", error);
+        }
+    }
+}
+
+pub fn defaults() -> Bindings {
+    let mut bindings = Bindings { chords: HashMap::new(), pad_inputs: HashMap::new() };
+    bindings.add(Action::Restart, KeyChord::plain(Key::R));
+    bindings.add(Action::Restart, KeyChord::new(Key::R, false, false, true, false));
+    bindings.add(Action::Quit, KeyChord::plain(Key::Q));
+    bindings.add(Action::Quit, KeyChord::new(Key::Q, false, true, false, false));
+    bindings.add(Action::OpenSettings, KeyChord::plain(Key::F1));
+    bindings.add(Action::Pause, KeyChord::plain(Key::PAUSE));
+    bindings.add(Action::Pause, KeyChord::plain(Key::P));
+    bindings.add(Action::CursorLeft, KeyChord::plain(Key::LEFT));
+    bindings.add(Action::CursorRight, KeyChord::plain(Key::RIGHT));
+    bindings.add(Action::Submit, KeyChord::plain(Key::ENTER));
+    bindings.add(Action::DeleteBackward, KeyChord::plain(Key::BACKSPACE));
+    bindings.add(Action::DeleteForward, KeyChord::plain(Key::DELETE));
+    bindings.add(Action::Hint, KeyChord::plain(Key::H));
+    bindings.add(Action::MenuUp, KeyChord::plain(Key::UP));
+    bindings.add(Action::MenuDown, KeyChord::plain(Key::DOWN));
+    bindings.add(Action::Back, KeyChord::plain(Key::ESCAPE));
+    // the button numbers of an Xbox style gamepad: A, B, X, Y, Back (Select) and Start
+    bindings.add_pad(Action::InputOne, PadInput::Button(0));
+    bindings.add_pad(Action::InputZero, PadInput::Button(1));
+    bindings.add_pad(Action::Submit, PadInput::Button(2));
+    bindings.add_pad(Action::Hint, PadInput::Button(3));
+    bindings.add_pad(Action::Restart, PadInput::Button(6));
+    bindings.add_pad(Action::Pause, PadInput::Button(7));
+    bindings.add_pad(Action::CursorLeft, PadInput::AxisNegative(Axis::POV_X));
+    bindings.add_pad(Action::CursorRight, PadInput::AxisPositive(Axis::POV_X));
+    bindings.add_pad(Action::MenuUp, PadInput::AxisNegative(Axis::POV_Y));
+    bindings.add_pad(Action::MenuDown, PadInput::AxisPositive(Axis::POV_Y));
+    bindings
+}
+
+// starts with the defaults and replaces every action, that is mentioned in the config file
+pub fn load() -> Bindings {
+    let config = config::load(CONFIG_PATH);
+    let mut bindings = defaults();
+    for action in Action::ALL {
+        if let Some(value) = config.get(&format!("bind.{}", action.name())) {
+            bindings.chords.remove(&action);
+            for chord_text in value.split(',').filter(|text| !text.trim().is_empty()) {
+                match KeyChord::parse(chord_text) {
+                    Some(chord) => bindings.add(action, chord),
+                    None => println!("Unknown key binding \"{}\" for {}", chord_text.trim(), action.name()),
+                }
+            }
+        }
+        if let Some(value) = config.get(&format!("pad.{}", action.name())) {
+            bindings.pad_inputs.remove(&action);
+            for pad_text in value.split(',').filter(|text| !text.trim().is_empty()) {
+                match PadInput::parse(pad_text) {
+                    Some(pad_input) => bindings.add_pad(action, pad_input),
+                    None => println!("Unknown joystick binding \"{}\" for {}", pad_text.trim(), action.name()),
+                }
+            }
+        }
+    }
+    bindings
+}
+
+const KEY_NAMES: [(&str, Key); 101] = [
+    ("A", Key::A), ("B", Key::B), ("C", Key::C), ("D", Key::D), ("E", Key::E), ("F", Key::F),
+    ("G", Key::G), ("H", Key::H), ("I", Key::I), ("J", Key::J), ("K", Key::K), ("L", Key::L),
+    ("M", Key::M), ("N", Key::N), ("O", Key::O), ("P", Key::P), ("Q", Key::Q), ("R", Key::R),
+    ("S", Key::S), ("T", Key::T), ("U", Key::U), ("V", Key::V), ("W", Key::W), ("X", Key::X),
+    ("Y", Key::Y), ("Z", Key::Z),
+    ("0", Key::NUM0), ("1", Key::NUM1), ("2", Key::NUM2), ("3", Key::NUM3), ("4", Key::NUM4),
+    ("5", Key::NUM5), ("6", Key::NUM6), ("7", Key::NUM7), ("8", Key::NUM8), ("9", Key::NUM9),
+    ("Escape", Key::ESCAPE), ("LControl", Key::LCONTROL), ("LShift", Key::LSHIFT), ("LAlt", Key::LALT),
+    ("LSystem", Key::LSYSTEM), ("RControl", Key::RCONTROL), ("RShift", Key::RSHIFT), ("RAlt", Key::RALT),
+    ("RSystem", Key::RSYSTEM), ("Menu", Key::MENU), ("LBracket", Key::LBRACKET), ("RBracket", Key::RBRACKET),
+    ("Semicolon", Key::SEMICOLON), ("Comma", Key::COMMA), ("Period", Key::PERIOD), ("Quote", Key::QUOTE),
+    ("Slash", Key::SLASH), ("Backslash", Key::BACKSLASH), ("Tilde", Key::TILDE), ("Equal", Key::EQUAL),
+    ("Hyphen", Key::HYPHEN), ("Space", Key::SPACE), ("Enter", Key::ENTER), ("Backspace", Key::BACKSPACE),
+    ("Tab", Key::TAB), ("PageUp", Key::PAGEUP), ("PageDown", Key::PAGEDOWN), ("End", Key::END),
+    ("Home", Key::HOME), ("Insert", Key::INSERT), ("Delete", Key::DELETE), ("Add", Key::ADD),
+    ("Subtract", Key::SUBTRACT), ("Multiply", Key::MULTIPLY), ("Divide", Key::DIVIDE), ("Left", Key::LEFT),
+    ("Right", Key::RIGHT), ("Up", Key::UP), ("Down", Key::DOWN),
+    ("Numpad0", Key::NUMPAD0), ("Numpad1", Key::NUMPAD1), ("Numpad2", Key::NUMPAD2), ("Numpad3", Key::NUMPAD3),
+    ("Numpad4", Key::NUMPAD4), ("Numpad5", Key::NUMPAD5), ("Numpad6", Key::NUMPAD6), ("Numpad7", Key::NUMPAD7),
+    ("Numpad8", Key::NUMPAD8), ("Numpad9", Key::NUMPAD9),
+    ("F1", Key::F1), ("F2", Key::F2), ("F3", Key::F3), ("F4", Key::F4), ("F5", Key::F5), ("F6", Key::F6),
+    ("F7", Key::F7), ("F8", Key::F8), ("F9", Key::F9), ("F10", Key::F10), ("F11", Key::F11), ("F12", Key::F12),
+    ("F13", Key::F13), ("F14", Key::F14), ("F15", Key::F15), ("Pause", Key::PAUSE),
+];
+
+fn key_from_name(name: &str) -> Option<Key> {
+    KEY_NAMES.iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+fn key_name(key: Key) -> Option<&'static str> {
+    KEY_NAMES.iter()
+        .find(|(_, named_key)| *named_key == key)
+        .map(|(key_name, _)| *key_name)
+}
+
+const AXIS_NAMES: [(&str, Axis); 8] = [
+    ("X", Axis::X), ("Y", Axis::Y), ("Z", Axis::Z), ("R", Axis::R),
+    ("U", Axis::U), ("V", Axis::V), ("PovX", Axis::POV_X), ("PovY", Axis::POV_Y),
+];
+
+fn axis_from_name(name: &str) -> Option<Axis> {
+    AXIS_NAMES.iter()
+        .find(|(axis_name, _)| axis_name.eq_ignore_ascii_case(name))
+        .map(|(_, axis)| *axis)
+}
+
+fn axis_name(axis: Axis) -> &'static str {
+    AXIS_NAMES.iter()
+        .find(|(_, named_axis)| *named_axis == axis)
+        .map(|(axis_name, _)| *axis_name)
+        .unwrap()
+}
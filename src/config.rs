@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::user_data;
+
+// A very small config file format:
+// every line is "key = value", lines starting with # are comments
+// keys are kept sorted, so the file stays readable after saving
+pub struct Config {
+    path: String,
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|value| value.as_str())
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    // every key, that starts with the prefix, in sorted order
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        self.values.iter()
+            .filter(move |(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn remove_prefix(&mut self, prefix: &str) {
+        self.values.retain(|key, _| !key.starts_with(prefix));
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut content = String::new();
+        for (key, value) in &self.values {
+            content.push_str(&format!("{} = {}\n", key, value));
+        }
+        user_data::write_atomically(Path::new(&self.path), &content)
+    }
+}
+
+// a missing or unreadable file just results in an empty config
+pub fn load(path: &str) -> Config {
+    let mut values = BTreeMap::new();
+    if let Ok(content) = fs::read_to_string(path) {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once('=') {
+                Some((key, value)) => { values.insert(key.trim().to_string(), value.trim().to_string()); }
+                None => println!("Ignoring malformed line in {}: {}", path, line),
+            }
+        }
+    }
+    Config {
+        path: path.to_string(),
+        values,
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+
+use crate::arguments;
+use crate::game::round_stats::{InputKind, InputRecord, RoundStats};
+use crate::game::session::Session;
+use crate::highscores;
+use crate::user_data;
+
+/*
+A session can be exported for teachers and for looking at it in a spreadsheet, as CSV and as JSON.
+Both carry the schema version, it goes up whenever a field changes its meaning or goes away
+(new fields at the end don't count). Times are seconds since the start of the round, the pause
+doesn't count; frames are the frames of the round. Fields without a value are empty in the CSV
+and null in the JSON.
+
+Version 1, JSON:
+    {
+      "schema_version": 1,
+      "exported_at": "2024-05-01T13:37:00Z",    UTC
+      "profile": "Anna",
+      "mode": "guided_msb_first",               the mode of the high scores, or "exercise_set"
+      "rounds": [
+        {
+          "round": 1,                           counted from 1
+          "number": 42,
+          "conversion": "dec→bin",
+          "level": 3,                           0 without adaptive difficulty
+          "outcome": "solved",                  "solved", "timed_out" or "quit"
+          "seconds": 12.5,
+          "wrong_attempts": 1,
+          "score": 90,
+          "inputs": [
+            {
+              "seconds": 1.25,
+              "frame": 75,
+              "kind": "digit",                  "digit", "toggle", "clear", "check" or "hint"
+              "slot": 0,                        counted from the left, null for a check
+              "expected": 1,                    the digit, that belongs into the slot
+              "actual": 0,                      what the player entered, null for a clear or hint
+              "correct": false,                 for a check: whether the whole answer was right
+              "hint_level": null                how detailed the hint got
+            }
+          ]
+        }
+      ]
+    }
+
+Version 1, CSV: one line per input, with the round repeated on every line. A round without any
+input still gets a line, with the input fields empty. The columns are:
+    schema_version,profile,mode,round,number,conversion,level,outcome,round_seconds,wrong_attempts,score,
+    seconds,frame,kind,slot,expected,actual,correct,hint_level
+ */
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const CSV_HEADER: &str = "schema_version,profile,mode,round,number,conversion,level,outcome,round_seconds,wrong_attempts,score,\
+seconds,frame,kind,slot,expected,actual,correct,hint_level";
+
+fn outcome(stats: &RoundStats) -> &'static str {
+    if stats.is_solved {
+        "solved"
+    } else if stats.is_timed_out {
+        "timed_out"
+    } else {
+        "quit"
+    }
+}
+
+fn hint_level(input: &InputRecord) -> Option<u8> {
+    match input.kind {
+        InputKind::Hint(level) => Some(level),
+        _ => None,
+    }
+}
+
+// "2024-05-01T13:37:00Z"
+fn timestamp(seconds: u64) -> String {
+    let time = seconds % 86_400;
+    format!("{}T{:02}:{:02}:{:02}Z", highscores::date_of(seconds), time / 3600, time / 60 % 60, time % 60)
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or(String::new(), |value| value.to_string())
+}
+
+pub fn to_csv(session: &Session, profile_name: &str) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    for (i, stats) in session.rounds().iter().enumerate() {
+        let round = [
+            SCHEMA_VERSION.to_string(),
+            csv_field(profile_name),
+            csv_field(session.mode()),
+            (i + 1).to_string(),
+            stats.number.to_string(),
+            stats.conversion.name().to_string(),
+            stats.level.to_string(),
+            outcome(stats).to_string(),
+            format!("{:.2}", stats.seconds),
+            stats.wrong_attempts.to_string(),
+            stats.score().to_string(),
+        ].join(",");
+        if stats.inputs.is_empty() {
+            lines.push(format!("{},,,,,,,,", round));
+        }
+        for input in &stats.inputs {
+            lines.push(format!("{},{:.2},{},{},{},{},{},{},{}", round, input.seconds, input.frame, input.kind.name(),
+                optional(input.slot), optional(input.expected), optional(input.actual), optional(input.is_correct), optional(hint_level(input))));
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if (character as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or("null".to_string(), |value| value.to_string())
+}
+
+fn input_to_json(input: &InputRecord) -> String {
+    format!("{{\"seconds\": {:.2}, \"frame\": {}, \"kind\": {}, \"slot\": {}, \"expected\": {}, \"actual\": {}, \"correct\": {}, \"hint_level\": {}}}",
+        input.seconds, input.frame, json_string(input.kind.name()), json_optional(input.slot), json_optional(input.expected),
+        json_optional(input.actual), json_optional(input.is_correct), json_optional(hint_level(input)))
+}
+
+pub fn to_json(session: &Session, profile_name: &str, exported_at: u64) -> String {
+    let rounds: Vec<String> = session.rounds().iter().enumerate()
+        .map(|(i, stats)| {
+            let inputs: Vec<String> = stats.inputs.iter().map(|input| format!("        {}", input_to_json(input))).collect();
+            let inputs = if inputs.is_empty() { "[]".to_string() } else { format!("[\n{}\n      ]", inputs.join(",\n")) };
+            format!("    {{\n      \"round\": {},\n      \"number\": {},\n      \"conversion\": {},\n      \"level\": {},\n      \"outcome\": {},\n      \
+                \"seconds\": {:.2},\n      \"wrong_attempts\": {},\n      \"score\": {},\n      \"inputs\": {}\n    }}",
+                i + 1, stats.number, json_string(stats.conversion.name()), stats.level, json_string(outcome(stats)),
+                stats.seconds, stats.wrong_attempts, stats.score(), inputs)
+        })
+        .collect();
+    format!("{{\n  \"schema_version\": {},\n  \"exported_at\": {},\n  \"profile\": {},\n  \"mode\": {},\n  \"rounds\": [\n{}\n  ]\n}}\n",
+        SCHEMA_VERSION, json_string(&timestamp(exported_at)), json_string(profile_name), json_string(session.mode()), rounds.join(",\n"))
+}
+
+// where the results screen puts its exports
+pub fn default_dir() -> PathBuf {
+    user_data::path("exports")
+}
+
+// --export <dir> writes every finished session into that directory
+pub fn dir_from_arguments() -> Option<PathBuf> {
+    arguments::value_of("--export").map(PathBuf::from)
+}
+
+// writes session-<date>-<time>.csv and .json and returns the path without the extension
+pub fn write(session: &Session, profile_name: &str, dir: &Path) -> std::io::Result<PathBuf> {
+    let now = highscores::now();
+    let base = dir.join(format!("session-{}", timestamp(now).replace(':', "").replace('T', "-").trim_end_matches('Z')));
+    user_data::write_atomically(&base.with_extension("csv"), &to_csv(session, profile_name))?;
+    user_data::write_atomically(&base.with_extension("json"), &to_json(session, profile_name, now))?;
+    Ok(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::round_stats::InputKind;
+    use crate::game::{exercise, round_stats, session};
+
+    // the fields of a csv line, commas inside quotes belong to the field
+    fn columns(line: &str) -> usize {
+        let mut is_quoted = false;
+        let mut count = 1;
+        for character in line.chars() {
+            match character {
+                '"' => is_quoted = !is_quoted,
+                ',' if !is_quoted => count += 1,
+                _ => {}
+            }
+        }
+        count
+    }
+
+    fn input(kind: InputKind, slot: Option<usize>) -> InputRecord {
+        InputRecord { seconds: 1.25, frame: 75, kind, slot, expected: Some(1), actual: None, is_correct: None }
+    }
+
+    fn session_of(mode: &str) -> Session {
+        let mut session = session::new(vec![exercise::plain(42), exercise::plain(7)], mode.to_string());
+        let mut played = round_stats::new(42);
+        played.is_solved = true;
+        played.seconds = 12.5;
+        played.inputs = vec![input(InputKind::Digit, Some(0)), input(InputKind::Hint(2), Some(3)), input(InputKind::Check, None)];
+        session.finish_round(played);
+        // quit before touching a slot
+        session.finish_round(round_stats::new(7));
+        session
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("Anna"), "Anna");
+        assert_eq!(csv_field("Müller, Jörg"), "\"Müller, Jörg\"");
+        assert_eq!(csv_field("the \"best\""), "\"the \"\"best\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("Zoë 🐧"), "\"Zoë 🐧\"");
+        assert_eq!(json_string("a \"b\" \\ c"), "\"a \\\"b\\\" \\\\ c\"");
+        assert_eq!(json_string("one\ntwo\tthree\u{1}"), "\"one\\ntwo\\tthree\\u0001\"");
+    }
+
+    #[test]
+    fn every_csv_line_has_every_column() {
+        let csv = to_csv(&session_of("guided_msb_first"), "Müller, \"Jo\"");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        let header = columns(CSV_HEADER);
+        assert_eq!(header, 19);
+        for line in &lines {
+            assert_eq!(columns(line), header, "{}", line);
+        }
+        assert!(lines[1].starts_with("1,\"Müller, \"\"Jo\"\"\",guided_msb_first,1,42,dec→bin,0,solved,12.50,0,"));
+        assert!(lines[1].ends_with(",1.25,75,digit,0,1,,,"));
+        assert!(lines[2].ends_with(",1.25,75,hint,3,1,,,2"));
+        assert!(lines[4].ends_with(",2,7,dec→bin,0,quit,0.00,0,0,,,,,,,,"), "{}", lines[4]);
+    }
+
+    #[test]
+    fn json_has_null_for_missing_values() {
+        let json = to_json(&session_of("exercise_set"), "Zoë", 0);
+        assert!(json.contains("\"exported_at\": \"1970-01-01T00:00:00Z\""));
+        assert!(json.contains("\"profile\": \"Zoë\""));
+        assert!(json.contains("\"kind\": \"check\", \"slot\": null, \"expected\": 1, \"actual\": null"));
+        assert!(json.contains("\"inputs\": []"));
+    }
+}
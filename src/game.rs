@@ -1,222 +1,476 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use sfml::graphics::RenderWindow;
-use sfml::system::{Clock, Vector2f};
-use sfml::window::Key;
+use sfml::system::{Vector2f, Vector2u};
+use sfml::window::mouse::Button;
 
+use crate::bindings::Action;
+use crate::game::board::Board;
+use crate::game::difficulty::Level;
+use crate::game::exercise::Exercise;
 use crate::game::game_tasks::GameTasks;
-use crate::game::input::Input;
+use crate::game::options::{BitOrder, EntryMode, GameOptions};
+use crate::game::round_stats::{HintRecord, InputKind, InputRecord, RoundStats};
 use crate::game::renderer::Renderer;
 use crate::game::snowman_states::SnowmanStates;
+use crate::game::speedrun::Ghost;
+use crate::resources::Resources;
 
-mod input;
-mod game_tasks;
-mod snowman_states;
-mod renderer;
+pub mod board;
+pub mod input;
+pub mod joystick;
+pub mod options;
+pub mod game_tasks;
+pub mod snowman_states;
+pub mod renderer;
 mod point_hoap;
+pub mod round_stats;
+pub mod session;
+pub mod scheduler;
+pub mod difficulty;
+pub mod number_source;
+pub mod exercise;
+pub mod analytics;
+pub mod recording;
+pub mod speedrun;
 
 pub struct Game {
     origin: Vec<char>,
-    game_solution_binary: Vec<u8>,
-    player_input: Vec<u8>,
+    board: Board,
+    stats: RoundStats,
     snowman_state: SnowmanStates,
+    // only a click, that started on the snowman, gets released on it
+    is_snowman_clicked: bool,
     snowman_animation_duration: i32,
-    input: Input,
+    options: GameOptions,
     renderer: Renderer,
+    // the sounds get played from there
+    resources: Rc<RefCell<Resources>>,
+    current_frame: i32,
     is_stopped: bool,
     is_user_terminated: bool,
+    // the round is lost, once the seconds of the stats reach it
+    time_limit: Option<f32>,
+    is_time_up: bool,
+    // the hint of an exercise set, it shows up once the player asks for a hint
+    note: Option<String>,
+    is_note_shown: bool,
+    // where the personal best of a speedrun is at the same moment
+    ghost: Option<Ghost>,
+    // a replay running alongside (like the ghost) makes no sound
+    is_muted: bool,
 }
 
 impl Game {
-    pub fn game_loop(&mut self, window: &mut RenderWindow) {
-        let origin_string = self.origin.clone().into_iter().collect::<String>();
-        println!("The chosen number is {}", origin_string);
-
-        let mut clock = Clock::start(); // Ich hoffe hier ist nicht zu viel falsch
-        let mut current_frame = 0;
-
-
-        loop {
-            // free snowman from his state, if it has ended
-            match self.snowman_state {
-                SnowmanStates::Waving(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+    // moves the animations on by one frame, the scene calls it once per frame
+    pub fn update(&mut self, frame_seconds: f32) {
+        let current_frame = self.current_frame;
+        // the clock stops, once the number is solved
+        if !self.board.is_solved() && !self.is_time_up {
+            self.stats.seconds += frame_seconds;
+            if self.time_limit.is_some_and(|limit| self.stats.seconds >= limit) && !self.is_celebrating() {
+                println!("Time is up");
+                self.is_time_up = true;
+                self.snowman_state = SnowmanStates::Melting(current_frame);
+                self.play("melt");
+            }
+        }
+        // free snowman from his state, if it has ended
+        match self.snowman_state {
+            SnowmanStates::Waving(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                SnowmanStates::Jumping(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+            }
+            SnowmanStates::Jumping(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                SnowmanStates::TakingTopHat(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::HoldingTopHat()
-                    }
+            }
+            SnowmanStates::TakingTopHat(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::HoldingTopHat()
                 }
-                SnowmanStates::PutTopHatBackOn(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+            }
+            SnowmanStates::PutTopHatBackOn(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                SnowmanStates::Melting(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Melted
+            }
+            SnowmanStates::Melting(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Melted;
+                    // a snowman, that melted because of the time, ends the round
+                    if self.is_time_up {
+                        println!("Round ended");
+                        self.is_stopped = true;
                     }
                 }
-                SnowmanStates::ResurrectionInProgress(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+            }
+            SnowmanStates::ResurrectionInProgress(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                SnowmanStates::Shrinking(_, start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+            }
+            SnowmanStates::Shrinking(_, start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                SnowmanStates::Growing(amplifire, start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Big(amplifire)
-                    }
+            }
+            SnowmanStates::Growing(amplifire, start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Big(amplifire)
                 }
-                SnowmanStates::MorphingIntoAFirTree(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::IsFirTree()
-                    }
+            }
+            SnowmanStates::MorphingIntoAFirTree(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::IsFirTree()
                 }
-                SnowmanStates::MorphingFromAFirTree(start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle;
-                        // exit game
-                        println!("Game ended, terminating...");
-                        self.is_stopped = true;
-                    }
+            }
+            SnowmanStates::MorphingFromAFirTree(start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle;
+                    // exit game
+                    println!("Round ended");
+                    self.is_stopped = true;
                 }
-                SnowmanStates::DeformationToAvoidPoint(px, py, start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::IsDeformedToAvoidPoint(px, py);
-                    }
+            }
+            SnowmanStates::DeformationToAvoidPoint(px, py, start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::IsDeformedToAvoidPoint(px, py);
                 }
-                SnowmanStates::ReverseDeformationToAvoidPoint(_, _, start_frame) => {
-                    if current_frame - start_frame >= self.snowman_animation_duration {
-                        self.snowman_state = SnowmanStates::Idle
-                    }
+            }
+            SnowmanStates::ReverseDeformationToAvoidPoint(_, _, start_frame) => {
+                if current_frame - start_frame >= self.snowman_animation_duration {
+                    self.snowman_state = SnowmanStates::Idle
                 }
-                _ => {}
             }
+            _ => {}
+        }
 
-            while let Some(event) = window.poll_event() {
-                match self.input.parse_input(event).unwrap() {
-                    GameTasks::Close => {
-                        self.is_user_terminated = true;
-                        self.is_stopped = true;
-                    },
-                    GameTasks::ClickPressed(_, x, y) => {
-                        if self.snowman_state != SnowmanStates::IsFirTree() {
-                            self.snowman_state = SnowmanStates::DeformationToAvoidPoint(x, y, current_frame)
+        // snowman idle generator
+        if self.snowman_state == SnowmanStates::Idle {
+            /*if rand::random::<i8>() == 0 {
+                self.snowman_state = SnowmanStates::Melting(current_frame);
+            }*/
+        }
+        self.current_frame += 1;
+    }
+
+    pub fn render(&mut self, window: &mut RenderWindow) {
+        let origin_string = self.origin.iter().collect::<String>();
+        self.renderer.render(window, &self.board, &origin_string, self.current_frame, self.snowman_state);
+
+        let mut status = Vec::new();
+        if self.stats.level > 0 {
+            status.push(format!("Level {}", self.stats.level));
+        }
+        if let Some(limit) = self.time_limit {
+            let left = (limit - self.stats.seconds).max(0.0).ceil() as u32;
+            status.push(format!("{}:{:02} left", left / 60, left % 60));
+        }
+        if let Some(ghost) = self.ghost {
+            status.push(match ghost.numbers_ahead {
+                0 => "Ghost: same number".to_string(),
+                ahead if ahead > 0 => format!("Ghost: {} ahead", ahead),
+                behind => format!("Ghost: {} behind", -behind),
+            });
+            if let Some(delta) = ghost.last_delta {
+                status.push(format!("Split {:+.2} s", delta));
+            }
+        }
+        if !status.is_empty() {
+            self.renderer.draw_status(window, &status.join("\n"));
+        }
+        if let Some(note) = self.note.as_ref().filter(|_| self.is_note_shown) {
+            self.renderer.draw_note(window, note);
+        }
+    }
+
+    // the round drawn see-through over another one, as the ghost of a speedrun
+    pub fn render_as_ghost(&self, window: &mut RenderWindow, numbers_ahead: i32) {
+        self.renderer.draw_ghost(window, &self.board, self.current_frame, self.snowman_state, numbers_ahead);
+    }
+
+    pub fn set_ghost(&mut self, ghost: Option<Ghost>) {
+        self.ghost = ghost;
+    }
+
+    // the seconds of the round so far, the pause doesn't count
+    pub fn seconds(&self) -> f32 {
+        self.stats.seconds
+    }
+
+    pub fn mute(&mut self) {
+        self.is_muted = true;
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.is_stopped
+    }
+
+    // leaves the round, like the quit action does
+    pub fn quit(&mut self) {
+        self.is_user_terminated = true;
+        self.is_stopped = true;
+    }
+
+    // what happened during the round so far
+    pub fn stats(&self) -> RoundStats {
+        let mut stats = self.stats.clone();
+        stats.wrong_attempts = self.board.wrong_attempts().len();
+        stats.is_solved = self.board.is_solved();
+        stats.is_timed_out = self.is_time_up;
+        stats.first_try = (0..self.board.len())
+            .map(|slot| {
+                self.board.slot(slot) == Some(self.board.expected(slot))
+                    && !self.board.wrong_attempts().iter().any(|attempt| attempt.slot == slot)
+                    && !self.stats.hints.iter().any(|hint| hint.slot == slot && hint.level == board::MAX_HINT_LEVEL)
+            })
+            .collect();
+        stats
+    }
+
+    // the settings and the pause are scenes of their own, the game scene takes care of them
+    pub fn handle_task(&mut self, task: GameTasks, window_size: Vector2u) {
+        let current_frame = self.current_frame;
+        // once the time is up, the answer can't be changed any more
+        if self.is_time_up && !matches!(task, GameTasks::Close | GameTasks::Triggered(Action::Quit)) {
+            return;
+        }
+        match task {
+            GameTasks::Close => self.quit(),
+            GameTasks::ClickPressed(button, x, y) => {
+                if self.snowman_state == SnowmanStates::IsFirTree() {
+                    self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
+                } else if let Some(slot) = renderer::slot_at(window_size, self.board.len(), x, y) {
+                    // left click flips the bit, right click empties the slot
+                    if !self.is_celebrating() {
+                        if button == Button::RIGHT {
+                            self.board.clear(slot);
+                            self.record_input(InputKind::Clear, Some(slot), None, None);
                         } else {
-                            self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
-                        }
-                    },
-                    GameTasks::ClickReleased(_, x, y) => self.snowman_state = SnowmanStates::ReverseDeformationToAvoidPoint(x, y, current_frame),
-                    //GameTasks::MouseWheelScrolled(wheel, delta, x, y) => , // TODO: let the snowman grow!
-                    GameTasks::Typed(key) => {
-                        if self.game_solution_binary.len() != self.player_input.len() {
-                            // where the game logic is hidden
-                            let input: u8 = match key {
-                                Key::NUM0 | Key::NUMPAD0 => 0,
-                                Key::NUM1 | Key::NUMPAD1 => 1,
-                                Key::NUM2 | Key::NUMPAD2 => 2,
-                                Key::NUM3 | Key::NUMPAD3 => 3,
-                                Key::NUM4 | Key::NUMPAD4 => 4,
-                                Key::NUM5 | Key::NUMPAD5 => 5,
-                                Key::NUM6 | Key::NUMPAD6 => 6,
-                                Key::NUM7 | Key::NUMPAD7 => 7,
-                                Key::NUM8 | Key::NUMPAD8 => 8,
-                                Key::NUM9 | Key::NUMPAD9 => 9,
-                                _ => 255, // for any other key, not bound to a event
-                            };
-
-                            if *self.game_solution_binary.get(self.player_input.len()).unwrap() == input && self.snowman_state != SnowmanStates::IsFirTree() {
-                                self.player_input.push(input);
-                                // TODO: Player guessed right event
-                                println!("You guessed right");
-                                if self.game_solution_binary.len() == self.player_input.len() {
-                                    println!("Game ended, playing win animation");
-                                    self.snowman_state = SnowmanStates::MorphingIntoAFirTree(current_frame);
-                                    // exiting this FirTree Morph exits the game
-                                }
-                            } else {
-                                // Todo: Player guessed wrong event
-                                println!("You guessed wrong");
-                            }
-                        } else if self.snowman_state == SnowmanStates::IsFirTree() {
-                            self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
+                            self.board.toggle(slot);
+                            self.record_input(InputKind::Toggle, Some(slot), self.board.slot(slot), None);
                         }
-                        match key {
-                            Key::R => {
-                                self.is_stopped = true;
-                            },
-                            Key::Q => {
-                                self.is_user_terminated = true;
-                                self.is_stopped = true;
-                            }
-                            _ => {}
-                        }
-                    },
-                    _ => {}
+                        self.board.move_cursor_to(slot);
+                    }
+                } else if renderer::submit_button_bounds(window_size).contains2(x as f32, y as f32) {
+                    self.submit(current_frame);
+                } else {
+                    self.snowman_state = SnowmanStates::DeformationToAvoidPoint(x, y, current_frame);
+                    self.is_snowman_clicked = true;
                 }
-            }
+            },
+            GameTasks::ClickReleased(_, x, y) if self.is_snowman_clicked => {
+                self.snowman_state = SnowmanStates::ReverseDeformationToAvoidPoint(x, y, current_frame);
+                self.is_snowman_clicked = false;
+            },
+            //GameTasks::MouseWheelScrolled(wheel, delta, x, y) => , // TODO: let the snowman grow!
+            GameTasks::Triggered(action) => match action {
+                Action::Restart => {
+                    self.is_stopped = true;
+                }
+                Action::Quit => self.quit(),
+                // the board ignores these, if there is no cursor (guided entry)
+                Action::CursorLeft => self.board.move_cursor_left(),
+                Action::CursorRight => self.board.move_cursor_right(),
+                Action::DeleteBackward if !self.is_celebrating() => {
+                    self.board.delete_backward();
+                    self.record_input(InputKind::Clear, self.board.cursor(), None, None);
+                }
+                Action::DeleteForward if !self.is_celebrating() => {
+                    self.board.delete_forward();
+                    self.record_input(InputKind::Clear, self.board.cursor(), None, None);
+                }
+                Action::DeleteBackward | Action::DeleteForward => {}
+                Action::InputOne => self.enter_digit(1, current_frame),
+                Action::InputZero => self.enter_digit(0, current_frame),
+                Action::Submit => self.submit(current_frame),
+                Action::Hint => self.show_hint(current_frame),
+                _ => {}
+            },
+            GameTasks::TextEntered(character) => {
+                // hex digits are understood too, a to f become 10 to 15
+                if let Some(input) = character.to_digit(16) {
+                    self.enter_digit(input as u8, current_frame);
+                }
+            },
+            // any key turns the fir tree back into a snowman
+            GameTasks::Typed(_) if self.snowman_state == SnowmanStates::IsFirTree() => {
+                self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
+            },
+            _ => {}
+        }
+    }
 
-            // snowman idle generator
-            if self.snowman_state == SnowmanStates::Idle {
-                /*if rand::random::<i8>() == 0 {
-                    self.snowman_state = SnowmanStates::Melting(current_frame);
-                }*/
+    fn enter_digit(&mut self, input: u8, current_frame: i32) {
+        if self.options.entry_mode == EntryMode::Free {
+            if self.snowman_state == SnowmanStates::IsFirTree() {
+                self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
+            } else if input as u32 >= self.board.base() {
+                println!("Only digits below {} fit into a slot", self.board.base());
+                self.play("wrong");
+                if let Some(cursor) = self.board.cursor() {
+                    self.board.record_wrong_attempt(cursor, input, current_frame);
+                    self.record_input(InputKind::Digit, Some(cursor), Some(input), None);
+                }
+            } else if !self.is_celebrating() {
+                self.record_input(InputKind::Digit, self.board.cursor(), Some(input), None);
+                self.board.write_at_cursor(input);
+            }
+            return;
+        }
+        match self.board.next_empty() {
+            Some(slot) => {
+                if self.snowman_state != SnowmanStates::IsFirTree() {
+                    self.record_input(InputKind::Digit, Some(slot), Some(input), None);
+                }
+                // where the game logic is hidden
+                if self.board.expected(slot) == input && self.snowman_state != SnowmanStates::IsFirTree() {
+                    self.board.confirm(slot, input);
+                    // TODO: Player guessed right event
+                    println!("You guessed right");
+                    self.play("right");
+                    if self.board.is_solved() {
+                        self.win(current_frame);
+                    }
+                } else {
+                    println!("You guessed wrong");
+                    self.play("wrong");
+                    self.board.record_wrong_attempt(slot, input, current_frame);
+                }
+            }
+            None if self.snowman_state == SnowmanStates::IsFirTree() => {
+                self.snowman_state = SnowmanStates::MorphingFromAFirTree(current_frame);
             }
-            self.renderer.render(window, &self.player_input, &origin_string, current_frame, self.snowman_state);
+            None => {}
+        }
+    }
+
+    // checks the whole answer at once, no matter how it got entered
+    fn submit(&mut self, current_frame: i32) {
+        if self.is_celebrating() {
+            return;
+        }
+        let is_correct = self.board.check(current_frame);
+        self.record_input(InputKind::Check, None, None, Some(is_correct));
+        let has_wrong_slot = (0..self.board.len()).any(|slot| self.board.feedback(slot) == Some(false));
+        if is_correct {
+            self.play("right");
+            self.win(current_frame);
+        } else if has_wrong_slot {
+            // the board remembers which bits were wrong, the renderer shows them
+            println!("Your answer is wrong");
+            self.play("wrong");
+        } else {
+            println!("Not every slot is filled yet");
+        }
+    }
 
-            // some things to get an fps counter
-            let elapsed_time = clock.elapsed_time().as_milliseconds();
-            let mut fps = 0;
-            if elapsed_time != 0 { // to remove a error, in which a number gets divided by zero
-                fps = 1000_i32 / elapsed_time;
+    // every press makes the hint for the current slot a bit more detailed, the last one gives the bit away
+    fn show_hint(&mut self, current_frame: i32) {
+        if self.is_celebrating() {
+            return;
+        }
+        let slot = match self.options.entry_mode {
+            EntryMode::Free => self.board.cursor(),
+            EntryMode::Guided => self.board.next_empty(),
+        };
+        let Some(slot) = slot else { return; };
+        self.is_note_shown = true;
+        let hint = self.board.escalate_hint(slot);
+        self.stats.hints.push(HintRecord { slot, level: hint.level });
+        self.record_input(InputKind::Hint(hint.level), Some(slot), None, None);
+        if hint.level == board::MAX_HINT_LEVEL {
+            let bit = self.board.expected(slot);
+            match self.options.entry_mode {
+                EntryMode::Free => self.board.write_at_cursor(bit),
+                EntryMode::Guided => {
+                    self.board.confirm(slot, bit);
+                    if self.board.is_solved() {
+                        self.win(current_frame);
+                    }
+                }
             }
-            window.set_title(&format!("Frametime: {}, FPS: {}", clock.elapsed_time().as_milliseconds(), fps));
-            clock.restart();
-            if self.is_stopped { break; }
-            current_frame += 1;
         }
     }
 
+    // for the export, a digit is correct when it's the expected one, a check tells itself
+    fn record_input(&mut self, kind: InputKind, slot: Option<usize>, actual: Option<u8>, is_correct: Option<bool>) {
+        let expected = slot.map(|slot| self.board.expected(slot));
+        let is_correct = is_correct.or_else(|| expected.zip(actual).map(|(expected, actual)| expected == actual));
+        self.stats.inputs.push(InputRecord {
+            seconds: self.stats.seconds,
+            frame: self.current_frame,
+            kind,
+            slot,
+            expected,
+            actual,
+            is_correct,
+        });
+    }
+
+    fn play(&self, sound: &str) {
+        if !self.is_muted {
+            self.resources.borrow_mut().play(sound);
+        }
+    }
+
+    fn win(&mut self, current_frame: i32) {
+        println!("Game ended, playing win animation");
+        self.snowman_state = SnowmanStates::MorphingIntoAFirTree(current_frame);
+        // exiting this FirTree Morph exits the game
+    }
+
+    fn is_celebrating(&self) -> bool {
+        matches!(self.snowman_state, SnowmanStates::MorphingIntoAFirTree(_) | SnowmanStates::IsFirTree() | SnowmanStates::MorphingFromAFirTree(_))
+    }
+
     pub fn got_closed_by_user(&self) -> bool {
         return self.is_user_terminated;
     }
 }
 
-pub fn new(number: u8, snowman_animation_duration: i32) -> Game {
-    // convert int to Vector of u8 holding single bits
-    let mut game_solution: Vec<u8> = Vec::<u8>::with_capacity(8);
-    for i in 0..8 { // ???? 8 ????
-        game_solution.push(number >> i & 1);
-    }
-    game_solution.reverse(); // destroys your mind but is the only implemented solution
+// The level decides the amount of bits (at least as many as the number needs), the time limit and which helpers are left.
+// The time limit of an exercise wins over the one of the level.
+pub fn new(exercise: &Exercise, level: Level, snowman_animation_duration: i32, options: GameOptions, resources: Rc<RefCell<Resources>>) -> Game {
+    let options = GameOptions {
+        show_place_values: options.show_place_values && level.show_place_values,
+        show_breakdown: options.show_breakdown && level.show_breakdown,
+        ..options
+    };
+    let number = exercise.number;
+    // convert int to Vector of u8 holding single digits
+    let game_solution = exercise.solution(level.bits);
 
     // convert int to Vector of char
     let mut origin: Vec<char> = Vec::<char>::with_capacity(8);
-    let number_as_string = number.to_string();
+    let number_as_string = exercise.origin(level.bits.max(8 - number.leading_zeros() as usize));
     for c in number_as_string.chars() {
         origin.push(c);
     }
+    println!("The chosen number is {} ({})", number, exercise.conversion.name());
 
     Game {
         origin,
-        game_solution_binary: game_solution,
-        player_input: Vec::with_capacity(8), // keine Lösung wurde angegeben
+        board: board::new(game_solution, exercise.conversion.base(), options.entry_mode == EntryMode::Free, options.bit_order == BitOrder::LsbFirst), // keine Lösung wurde angegeben
+        stats: round_stats::RoundStats { level: level.number, conversion: exercise.conversion, ..round_stats::new(number as u32) },
         snowman_state: SnowmanStates::Idle,
+        is_snowman_clicked: false,
         snowman_animation_duration,
-        input: input::new(),
-        // TODO: Renderer initialization geht gar nicht
-        renderer: renderer::new(Vector2f::new(0.0, 600.0), snowman_animation_duration),
+        options,
+        renderer: renderer::new(Vector2f::new(0.0, 600.0), snowman_animation_duration, options, Rc::clone(&resources)),
+        resources,
+        current_frame: 0,
         is_stopped: false,
         is_user_terminated: false,
+        time_limit: exercise.time_limit.or(level.time_limit),
+        is_time_up: false,
+        note: exercise.hint.clone(),
+        is_note_shown: false,
+        ghost: None,
+        is_muted: false,
     }
 }
@@ -0,0 +1,118 @@
+use crate::config::Config;
+use crate::game::round_stats::{InputKind, RoundStats};
+
+/*
+Where a profile keeps going wrong, for the statistics screen.
+Every bit position (2^0 to 2^7) counts the bits entered there, how many of them were wrong
+and how long the player took for them (since the input before, or the start of the round).
+Only binary answers count here, a digit of a decimal or hex answer has no bit position.
+Every number counts its rounds, the missed ones (not flawless) and the seconds they took.
+ */
+
+pub const BIT_POSITIONS: usize = 8;
+
+#[derive(Copy, Clone, Default)]
+pub struct Tally {
+    pub count: u32,
+    pub wrong: u32,
+    pub seconds: f32,
+}
+
+impl Tally {
+    pub fn error_rate(&self) -> Option<f32> {
+        if self.count == 0 { None } else { Some(self.wrong as f32 / self.count as f32) }
+    }
+
+    pub fn mean_seconds(&self) -> Option<f32> {
+        if self.count == 0 { None } else { Some(self.seconds / self.count as f32) }
+    }
+
+    fn add(&mut self, is_wrong: bool, seconds: f32) {
+        self.count += 1;
+        self.wrong += is_wrong as u32;
+        self.seconds += seconds;
+    }
+}
+
+pub struct Analytics {
+    // indexed by the exponent of the place value, bits[7] is the 128
+    pub bits: [Tally; BIT_POSITIONS],
+    pub numbers: [Tally; 256],
+}
+
+impl Analytics {
+    pub fn record(&mut self, stats: &RoundStats) {
+        if let Ok(number) = u8::try_from(stats.number) {
+            self.numbers[number as usize].add(!stats.is_flawless(), stats.seconds);
+        }
+        if !stats.conversion.is_binary() {
+            return;
+        }
+        // the slots go from left to right, so the first one has the highest place value
+        let width = stats.first_try.len();
+        let mut last_input = 0.0;
+        for input in &stats.inputs {
+            let latency = input.seconds - last_input;
+            last_input = input.seconds;
+            if !matches!(input.kind, InputKind::Digit | InputKind::Toggle) {
+                continue;
+            }
+            let (Some(slot), Some(is_correct)) = (input.slot, input.is_correct) else { continue };
+            let position = width - 1 - slot;
+            if position < BIT_POSITIONS {
+                self.bits[position].add(!is_correct, latency);
+            }
+        }
+    }
+
+    pub fn has_data(&self) -> bool {
+        self.numbers.iter().any(|tally| tally.count > 0)
+    }
+
+    // only into the profile, writing it is up to the caller
+    pub fn save_to(&self, profile: &mut Config) {
+        profile.remove_prefix("analytics.");
+        for (position, tally) in self.bits.iter().enumerate() {
+            if tally.count > 0 {
+                profile.set(&format!("analytics.bit.{}", position), &format_tally(tally));
+            }
+        }
+        for (number, tally) in self.numbers.iter().enumerate() {
+            if tally.count > 0 {
+                profile.set(&format!("analytics.number.{:03}", number), &format_tally(tally));
+            }
+        }
+    }
+}
+
+fn format_tally(tally: &Tally) -> String {
+    format!("{}\t{}\t{:.2}", tally.count, tally.wrong, tally.seconds)
+}
+
+fn parse_tally(value: &str) -> Option<Tally> {
+    let mut fields = value.split('\t');
+    Some(Tally {
+        count: fields.next()?.parse().ok()?,
+        wrong: fields.next()?.parse().ok()?,
+        seconds: fields.next()?.parse().ok()?,
+    })
+}
+
+// reads "<prefix><index> = count\twrong\tseconds" into the tallies
+fn parse_tallies(profile: &Config, prefix: &str, tallies: &mut [Tally]) {
+    for (key, value) in profile.with_prefix(prefix) {
+        let index = key[prefix.len()..].parse::<usize>().ok().filter(|index| *index < tallies.len());
+        match (index, parse_tally(value)) {
+            (Some(index), Some(tally)) => tallies[index] = tally,
+            _ => println!("Ignoring malformed statistics {} = {}", key, value),
+        }
+    }
+}
+
+// the statistics of the profile in use ("analytics." keys)
+pub fn load(profile: &Config) -> Analytics {
+    let mut analytics = Analytics { bits: [Tally::default(); BIT_POSITIONS], numbers: [Tally::default(); 256] };
+    parse_tallies(profile, "analytics.bit.", &mut analytics.bits);
+    parse_tallies(profile, "analytics.number.", &mut analytics.numbers);
+    analytics
+}
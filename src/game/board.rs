@@ -0,0 +1,259 @@
+// a bit, that got rejected
+#[derive(Copy, Clone)]
+pub struct WrongAttempt {
+    pub slot: usize,
+    pub value: u8,
+    pub frame: i32,
+}
+
+// The hint for one slot, it gets more detailed every time it is asked for:
+// level 1 points at the biggest power of two (or of the base), that still fits,
+// level 2 shows what is left after subtracting it and level 3 reveals the bit
+#[derive(Copy, Clone)]
+pub struct Hint {
+    pub slot: usize,
+    pub level: u8,
+    // what is left of the number, once the bits left of the slot are taken away
+    pub remaining: u32,
+    pub largest_power: Option<u32>,
+}
+
+pub const MAX_HINT_LEVEL: u8 = 3;
+
+// The slots the player fills in, next to the solution they are checked against.
+// Slot 0 is the one on the very left (the most significant bit).
+// Mostly the slots hold bits, converting to decimal or hex puts digits of that base into them.
+pub struct Board {
+    solution: Vec<u8>,
+    base: u32,
+    slots: Vec<Option<u8>>,
+    // only the free entry mode has a cursor
+    cursor: Option<usize>,
+    // the bits get entered from right to left
+    is_lsb_first: bool,
+    // whether a slot was right, the last time the answer got checked
+    // editing a slot forgets its feedback
+    feedback: Vec<Option<bool>>,
+    wrong_attempts: Vec<WrongAttempt>,
+    hint: Option<Hint>,
+}
+
+impl Board {
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn slot(&self, slot: usize) -> Option<u8> {
+        self.slots[slot]
+    }
+
+    pub fn expected(&self, slot: usize) -> u8 {
+        self.solution[slot]
+    }
+
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    pub fn is_lsb_first(&self) -> bool {
+        self.is_lsb_first
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    // the number the solution stands for
+    pub fn solution_value(&self) -> u32 {
+        self.solution.iter().fold(0, |value, digit| value * self.base + *digit as u32)
+    }
+
+    // how many slots, counted from the right, are filled without a gap
+    pub fn filled_from_right(&self) -> usize {
+        self.slots.iter().rev().take_while(|slot| slot.is_some()).count()
+    }
+
+    pub fn feedback(&self, slot: usize) -> Option<bool> {
+        self.feedback[slot]
+    }
+
+    // the slot the guided entry fills next, depending on the direction of entry
+    pub fn place_value(&self, slot: usize) -> u32 {
+        self.base.pow((self.len() - 1 - slot) as u32)
+    }
+
+    // what is left of the number, once the bits on the left of the slot are taken away
+    pub fn remaining_before(&self, slot: usize) -> u32 {
+        self.solution_value() - (0..slot).map(|left| self.solution[left] as u32 * self.place_value(left)).sum::<u32>()
+    }
+
+    pub fn hint(&self) -> Option<&Hint> {
+        self.hint.as_ref()
+    }
+
+    // shows the next level of the hint for a slot and returns it
+    pub fn escalate_hint(&mut self, slot: usize) -> Hint {
+        let level = match self.hint {
+            Some(hint) if hint.slot == slot => (hint.level + 1).min(MAX_HINT_LEVEL),
+            _ => 1,
+        };
+        let remaining = self.remaining_before(slot);
+        let largest_power = if remaining == 0 { None } else { Some(self.base.pow(remaining.ilog(self.base))) };
+        let hint = Hint { slot, level, remaining, largest_power };
+        self.hint = Some(hint);
+        hint
+    }
+
+    pub fn wrong_attempts(&self) -> &[WrongAttempt] {
+        &self.wrong_attempts
+    }
+
+    pub fn last_wrong_attempt(&self) -> Option<&WrongAttempt> {
+        self.wrong_attempts.last()
+    }
+
+    pub fn record_wrong_attempt(&mut self, slot: usize, value: u8, frame: i32) {
+        self.wrong_attempts.push(WrongAttempt { slot, value, frame });
+    }
+
+    pub fn next_empty(&self) -> Option<usize> {
+        if self.is_lsb_first {
+            self.slots.iter().rposition(|slot| slot.is_none())
+        } else {
+            self.slots.iter().position(|slot| slot.is_none())
+        }
+    }
+
+    pub fn set(&mut self, slot: usize, value: u8) {
+        self.slots[slot] = Some(value);
+        self.feedback[slot] = None;
+        self.forget_hint(slot);
+    }
+
+    // sets a bit, that is already known to be right
+    pub fn confirm(&mut self, slot: usize, value: u8) {
+        self.slots[slot] = Some(value);
+        self.feedback[slot] = Some(true);
+        self.forget_hint(slot);
+    }
+
+    pub fn clear(&mut self, slot: usize) {
+        self.slots[slot] = None;
+        self.feedback[slot] = None;
+        self.forget_hint(slot);
+    }
+
+    // a hint is only about the slot, as long as nobody touched it
+    fn forget_hint(&mut self, slot: usize) {
+        if self.hint.is_some_and(|hint| hint.slot == slot) {
+            self.hint = None;
+        }
+    }
+
+    // flips the bit in a slot, an empty slot counts as 0
+    // a digit of a bigger base counts up instead and starts over after the biggest one
+    pub fn toggle(&mut self, slot: usize) {
+        let next = match self.slots[slot] {
+            Some(digit) => (digit as u32 + 1) % self.base,
+            None => 1,
+        };
+        self.set(slot, next as u8);
+    }
+
+    pub fn move_cursor_to(&mut self, slot: usize) {
+        if self.cursor.is_some() {
+            self.cursor = Some(slot.min(self.len() - 1));
+        }
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        if let Some(cursor) = self.cursor {
+            self.cursor = Some(cursor.saturating_sub(1));
+        }
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        if let Some(cursor) = self.cursor {
+            self.cursor = Some((cursor + 1).min(self.len() - 1));
+        }
+    }
+
+    // forward is the direction of entry
+    fn move_cursor_forward(&mut self) {
+        if self.is_lsb_first { self.move_cursor_left() } else { self.move_cursor_right() }
+    }
+
+    fn move_cursor_backward(&mut self) {
+        if self.is_lsb_first { self.move_cursor_right() } else { self.move_cursor_left() }
+    }
+
+    // overwrites the slot under the cursor and moves on to the next one
+    pub fn write_at_cursor(&mut self, value: u8) {
+        if let Some(cursor) = self.cursor {
+            self.set(cursor, value);
+            self.move_cursor_forward();
+        }
+    }
+
+    // like in a text field: if the slot under the cursor is empty, the one before it gets removed
+    pub fn delete_backward(&mut self) {
+        if let Some(cursor) = self.cursor {
+            if self.slots[cursor].is_none() {
+                self.move_cursor_backward();
+            }
+            let cursor = self.cursor.unwrap();
+            self.clear(cursor);
+        }
+    }
+
+    pub fn delete_forward(&mut self) {
+        if let Some(cursor) = self.cursor {
+            self.clear(cursor);
+        }
+    }
+
+    // remembers for every filled slot, whether it is right, and returns true if all of them are
+    // every wrong bit counts as a wrong attempt, an empty slot is only not done yet
+    pub fn check(&mut self, frame: i32) -> bool {
+        for slot in 0..self.len() {
+            let Some(digit) = self.slots[slot] else {
+                self.feedback[slot] = None;
+                continue;
+            };
+            let is_right = digit == self.solution[slot];
+            self.feedback[slot] = Some(is_right);
+            if !is_right {
+                self.record_wrong_attempt(slot, digit, frame);
+            }
+        }
+        self.is_solved()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.slots.iter().zip(self.solution.iter()).all(|(slot, expected)| *slot == Some(*expected))
+    }
+}
+
+// the bits of a number, the most significant one first
+// the slots always show it on the left, the bit order only decides from which side they get filled
+pub fn bits_of(number: u32, width: usize) -> Vec<u8> {
+    digits_of(number, 2, width)
+}
+
+pub fn digits_of(number: u32, base: u32, width: usize) -> Vec<u8> {
+    (0..width).rev().map(|i| (number / base.pow(i as u32) % base) as u8).collect()
+}
+
+pub fn new(solution: Vec<u8>, base: u32, has_cursor: bool, is_lsb_first: bool) -> Board {
+    let first_slot = if is_lsb_first { solution.len() - 1 } else { 0 };
+    Board {
+        slots: vec![None; solution.len()],
+        cursor: if has_cursor { Some(first_slot) } else { None },
+        is_lsb_first,
+        feedback: vec![None; solution.len()],
+        wrong_attempts: Vec::new(),
+        hint: None,
+        solution,
+        base,
+    }
+}
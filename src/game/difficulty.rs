@@ -0,0 +1,206 @@
+use crate::game::round_stats::RoundStats;
+use crate::config::Config;
+
+// how a round is made harder, every level adds one thing to the one before
+#[derive(Copy, Clone)]
+pub struct Level {
+    // counted from 1, 0 is the fixed difficulty of a game without adaptation
+    pub number: usize,
+    pub bits: usize,
+    // the biggest number, that gets asked
+    pub max: u8,
+    // a round is lost, once the time runs out
+    pub time_limit: Option<f32>,
+    pub show_place_values: bool,
+    pub show_breakdown: bool,
+}
+
+const fn level(number: usize, bits: usize, max: u8, time_limit: Option<f32>, show_place_values: bool, show_breakdown: bool) -> Level {
+    Level { number, bits, max, time_limit, show_place_values, show_breakdown }
+}
+
+pub const LEVELS: [Level; 9] = [
+    level(1, 3, 7, None, true, true),
+    level(2, 4, 15, None, true, true),
+    level(3, 5, 31, None, true, true),
+    level(4, 6, 63, None, true, true),
+    level(5, 8, 255, None, true, true),
+    level(6, 8, 255, Some(60.0), true, false),
+    level(7, 8, 255, Some(45.0), false, false),
+    level(8, 8, 255, Some(30.0), false, false),
+    level(9, 8, 255, Some(20.0), false, false),
+];
+
+// without adaptation the game plays as it always did, the helpers follow the options
+pub const FIXED: Level = level(0, 8, 255, None, true, true);
+
+// that many good rounds in a row make the next round harder, that many missed ones make it easier
+const ROUNDS_TO_LEVEL_UP: u32 = 3;
+const ROUNDS_TO_LEVEL_DOWN: u32 = 2;
+// a round counts as good, when it's flawless and takes at most this long per bit
+const GOOD_SECONDS_PER_BIT: f32 = 2.5;
+
+// The difficulty of a profile follows its recent rounds: good ones in a row move it up a level,
+// missed ones in a row move it down again. Rounds in between (solved with a mistake, or slowly) keep it.
+pub struct Difficulty {
+    level_index: usize,
+    good_rounds: u32,
+    missed_rounds: u32,
+}
+
+impl Difficulty {
+    pub fn level(&self) -> Level {
+        LEVELS[self.level_index]
+    }
+
+    // returns true, if the level changed
+    pub fn record(&mut self, stats: &RoundStats) -> bool {
+        let is_good = stats.is_flawless() && stats.seconds <= GOOD_SECONDS_PER_BIT * stats.first_try.len() as f32;
+        if is_good {
+            self.good_rounds += 1;
+            self.missed_rounds = 0;
+        } else if !stats.is_solved || stats.revealed_bits() > 0 {
+            self.missed_rounds += 1;
+            self.good_rounds = 0;
+        } else {
+            self.good_rounds = 0;
+            self.missed_rounds = 0;
+        }
+
+        let old_index = self.level_index;
+        if self.good_rounds >= ROUNDS_TO_LEVEL_UP && self.level_index + 1 < LEVELS.len() {
+            self.level_index += 1;
+        } else if self.missed_rounds >= ROUNDS_TO_LEVEL_DOWN && self.level_index > 0 {
+            self.level_index -= 1;
+        }
+        if self.level_index != old_index {
+            self.good_rounds = 0;
+            self.missed_rounds = 0;
+            println!("Difficulty changed to level {}", self.level().number);
+        }
+        self.level_index != old_index
+    }
+
+    // only into the profile, writing it is up to the caller
+    pub fn save_to(&self, profile: &mut Config) {
+        profile.set("difficulty.level", &self.level().number.to_string());
+        profile.set("difficulty.good_rounds", &self.good_rounds.to_string());
+        profile.set("difficulty.missed_rounds", &self.missed_rounds.to_string());
+    }
+}
+
+// 0 is the fixed difficulty
+pub fn by_number(number: usize) -> Level {
+    match number {
+        0 => FIXED,
+        _ => LEVELS[number.min(LEVELS.len()) - 1],
+    }
+}
+
+// where the profile in use left off, a new one starts at the easiest level
+pub fn load(profile: &Config) -> Difficulty {
+    let number = |key: &str| profile.get(key).and_then(|value| value.parse::<u32>().ok()).unwrap_or(0);
+    Difficulty {
+        level_index: (number("difficulty.level") as usize).clamp(1, LEVELS.len()) - 1,
+        good_rounds: number("difficulty.good_rounds"),
+        missed_rounds: number("difficulty.missed_rounds"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use crate::game::round_stats;
+
+    // 3 bits answered flawlessly in time
+    fn good() -> RoundStats {
+        let mut stats = round_stats::new(5);
+        stats.is_solved = true;
+        stats.seconds = 1.0;
+        stats.first_try = vec![true; 3];
+        stats
+    }
+
+    fn with_a_mistake() -> RoundStats {
+        let mut stats = good();
+        stats.wrong_attempts = 1;
+        stats
+    }
+
+    fn missed() -> RoundStats {
+        let mut stats = good();
+        stats.is_solved = false;
+        stats
+    }
+
+    fn at_level(number: usize) -> Difficulty {
+        let mut profile = config::load("");
+        profile.set("difficulty.level", &number.to_string());
+        load(&profile)
+    }
+
+    #[test]
+    fn three_good_rounds_go_up_a_level() {
+        let mut difficulty = at_level(1);
+        assert!(!difficulty.record(&good()));
+        assert!(!difficulty.record(&good()));
+        assert!(difficulty.record(&good()));
+        assert_eq!(difficulty.level().number, 2);
+
+        // a slow round isn't good
+        let mut slow = good();
+        slow.seconds = 10.0;
+        difficulty.record(&slow);
+        assert_eq!(difficulty.level().number, 2);
+    }
+
+    #[test]
+    fn two_missed_rounds_go_down_a_level() {
+        let mut difficulty = at_level(5);
+        assert!(!difficulty.record(&missed()));
+        assert!(difficulty.record(&missed()));
+        assert_eq!(difficulty.level().number, 4);
+    }
+
+    #[test]
+    fn a_round_in_between_breaks_the_streak() {
+        let mut difficulty = at_level(5);
+        difficulty.record(&good());
+        difficulty.record(&good());
+        difficulty.record(&with_a_mistake());
+        difficulty.record(&good());
+        difficulty.record(&missed());
+        difficulty.record(&with_a_mistake());
+        difficulty.record(&missed());
+        assert_eq!(difficulty.level().number, 5);
+    }
+
+    #[test]
+    fn the_levels_stop_at_both_ends() {
+        let mut difficulty = at_level(1);
+        for _ in 0..4 {
+            difficulty.record(&missed());
+        }
+        assert_eq!(difficulty.level().number, 1);
+        let mut difficulty = at_level(LEVELS.len());
+        for _ in 0..6 {
+            difficulty.record(&good());
+        }
+        assert_eq!(difficulty.level().number, LEVELS.len());
+    }
+
+    #[test]
+    fn the_profile_keeps_the_level_and_the_streak() {
+        let mut difficulty = at_level(3);
+        difficulty.record(&good());
+        difficulty.record(&good());
+        let mut profile = config::load("");
+        difficulty.save_to(&mut profile);
+        let mut loaded = load(&profile);
+        assert_eq!(loaded.level().number, 3);
+        assert!(loaded.record(&good()));
+        assert_eq!(loaded.level().number, 4);
+        assert_eq!(load(&config::load("")).level().number, 1);
+    }
+}
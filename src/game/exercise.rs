@@ -0,0 +1,287 @@
+use std::fs;
+
+use crate::game::board;
+
+// what a number gets converted from and to
+#[derive(Copy, Clone, PartialEq)]
+pub enum Conversion {
+    DecToBin,
+    BinToDec,
+    DecToHex,
+    HexToBin,
+    BinToHex,
+}
+
+impl Conversion {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conversion::DecToBin => "dec→bin",
+            Conversion::BinToDec => "bin→dec",
+            Conversion::DecToHex => "dec→hex",
+            Conversion::HexToBin => "hex→bin",
+            Conversion::BinToHex => "bin→hex",
+        }
+    }
+
+    // the base of the digits the player enters
+    pub fn base(&self) -> u32 {
+        match self {
+            Conversion::DecToBin | Conversion::HexToBin => 2,
+            Conversion::BinToDec => 10,
+            Conversion::DecToHex | Conversion::BinToHex => 16,
+        }
+    }
+
+    // only the digits of a binary answer are bits with a place value
+    pub fn is_binary(&self) -> bool {
+        self.base() == 2
+    }
+}
+
+// "dec→bin", "dec->bin", "dec_to_bin", "dec2bin" and "dec-bin" all mean the same, "bin" alone is short for dec→bin
+fn parse_conversion(text: &str) -> Option<Conversion> {
+    let normalized = text.to_lowercase().replace('→', "-").replace("->", "-").replace("_to_", "-").replace('2', "-");
+    match normalized.as_str() {
+        "dec-bin" | "bin" => Some(Conversion::DecToBin),
+        "bin-dec" | "dec" => Some(Conversion::BinToDec),
+        "dec-hex" | "hex" => Some(Conversion::DecToHex),
+        "hex-bin" => Some(Conversion::HexToBin),
+        "bin-hex" => Some(Conversion::BinToHex),
+        _ => None,
+    }
+}
+
+// one number to convert, the normal game only asks for dec→bin without a limit or a hint of its own
+#[derive(Clone)]
+pub struct Exercise {
+    pub number: u8,
+    pub conversion: Conversion,
+    // replaces the time limit of the difficulty level
+    pub time_limit: Option<f32>,
+    // shown once the player asks for a hint
+    pub hint: Option<String>,
+}
+
+impl Exercise {
+    // the digits the player has to enter, binary ones get at least the given amount of bits
+    pub fn solution(&self, bits: usize) -> Vec<u8> {
+        let number = self.number as u32;
+        match self.conversion {
+            Conversion::DecToBin | Conversion::HexToBin => board::bits_of(number, bits.max(8 - self.number.leading_zeros() as usize)),
+            Conversion::BinToDec => board::digits_of(number, 10, number.max(1).ilog10() as usize + 1),
+            Conversion::DecToHex | Conversion::BinToHex => board::digits_of(number, 16, 2),
+        }
+    }
+
+    // how the number is shown to the player
+    pub fn origin(&self, bits: usize) -> String {
+        match self.conversion {
+            Conversion::DecToBin | Conversion::DecToHex => self.number.to_string(),
+            Conversion::BinToDec | Conversion::BinToHex => format!("{:0width$b}", self.number, width = bits),
+            Conversion::HexToBin => format!("0x{:02X}", self.number),
+        }
+    }
+
+    // the line of an exercise set, that reads as this exercise again
+    pub fn to_line(&self) -> String {
+        let time_limit = self.time_limit.map_or("-".to_string(), |seconds| seconds.to_string());
+        format!("{} {} {} {}", self.number, self.conversion.name(), time_limit, self.hint.as_deref().unwrap_or("")).trim_end().to_string()
+    }
+}
+
+pub fn plain(number: u8) -> Exercise {
+    Exercise { number, conversion: Conversion::DecToBin, time_limit: None, hint: None }
+}
+
+/*
+An exercise set has one exercise per line: the number, the conversion, a time limit in seconds and a hint.
+Only the number is needed, without a conversion it's dec→bin, "-" skips the time limit.
+Numbers can be written as 42, 0b101010 or 0x2A. Empty lines and lines starting with # are ignored.
+The simple format separates the fields with spaces, everything after the time limit is the hint:
+    42 dec→bin 30 Start with the 32
+    0x2A hex→bin
+A .csv file separates them with commas instead, the hint can be quoted, a header line is allowed:
+    number,conversion,time_limit,hint
+    42,dec-bin,30,"32 fits, what is left?"
+ */
+
+fn parse_number(text: &str) -> Result<u8, String> {
+    let parsed = if let Some(binary) = text.strip_prefix("0b") {
+        u8::from_str_radix(binary, 2)
+    } else if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u8::from_str_radix(hex, 16)
+    } else {
+        text.parse()
+    };
+    parsed.map_err(|_| format!("\"{}\" is no number from 0 to 255", text))
+}
+
+fn parse_time_limit(text: &str) -> Result<Option<f32>, String> {
+    match text {
+        "" | "-" => Ok(None),
+        _ => match text.parse::<f32>() {
+            Ok(seconds) if seconds > 0.0 => Ok(Some(seconds)),
+            _ => Err(format!("\"{}\" is no time limit, it has to be seconds above 0 or -", text)),
+        },
+    }
+}
+
+// the fields of a csv line, commas inside quotes don't split and "" is a quote
+fn split_csv(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![String::new()];
+    let mut is_quoted = false;
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if is_quoted && characters.peek() == Some(&'"') => {
+                characters.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => is_quoted = !is_quoted,
+            ',' if !is_quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(character),
+        }
+    }
+    if is_quoted {
+        return Err("a quote is never closed".to_string());
+    }
+    Ok(fields.into_iter().map(|field| field.trim().to_string()).collect())
+}
+
+// cuts the first word off the line
+fn next_word<'a>(rest: &mut &'a str) -> &'a str {
+    let trimmed = rest.trim_start();
+    let (word, after) = trimmed.split_at(trimmed.find(char::is_whitespace).unwrap_or(trimmed.len()));
+    *rest = after;
+    word
+}
+
+fn parse_fields(number: &str, conversion: Option<&str>, time_limit: Option<&str>, hint: Option<&str>) -> Result<Exercise, String> {
+    let conversion = match conversion.filter(|conversion| !conversion.is_empty()) {
+        Some(text) => parse_conversion(text).ok_or_else(|| format!("\"{}\" is no conversion, try dec→bin, bin→dec, dec→hex, hex→bin or bin→hex", text))?,
+        None => Conversion::DecToBin,
+    };
+    Ok(Exercise {
+        number: parse_number(number)?,
+        conversion,
+        time_limit: parse_time_limit(time_limit.unwrap_or(""))?,
+        hint: hint.map(str::trim).filter(|hint| !hint.is_empty()).map(str::to_string),
+    })
+}
+
+// every line, that can't be read, is reported with its line number, instead of playing half a set
+pub fn parse(content: &str, is_csv: bool) -> Result<Vec<Exercise>, Vec<String>> {
+    let mut exercises = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parsed = if is_csv {
+            match split_csv(line) {
+                // the header of a spreadsheet
+                Ok(fields) if exercises.is_empty() && errors.is_empty() && fields[0].eq_ignore_ascii_case("number") => continue,
+                Ok(fields) if fields.len() > 4 => Err(format!("{} fields, but there are only 4", fields.len())),
+                Ok(fields) => parse_fields(&fields[0], fields.get(1).map(String::as_str), fields.get(2).map(String::as_str), fields.get(3).map(String::as_str)),
+                Err(error) => Err(error),
+            }
+        } else {
+            let mut rest = line;
+            let number = next_word(&mut rest);
+            let conversion = next_word(&mut rest);
+            let time_limit = next_word(&mut rest);
+            parse_fields(number, Some(conversion), Some(time_limit), Some(rest))
+        };
+        match parsed {
+            Ok(exercise) => exercises.push(exercise),
+            Err(error) => errors.push(format!("line {}: {}", i + 1, error)),
+        }
+    }
+    if exercises.is_empty() && errors.is_empty() {
+        errors.push("there is no exercise in it".to_string());
+    }
+    if errors.is_empty() { Ok(exercises) } else { Err(errors) }
+}
+
+pub fn load(path: &str) -> Result<Vec<Exercise>, Vec<String>> {
+    let content = fs::read_to_string(path).map_err(|error| vec![format!("could not read {}: {}", path, error)])?;
+    parse(&content, path.to_lowercase().ends_with(".csv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(content: &str, is_csv: bool) -> Vec<String> {
+        parse(content, is_csv).err().expect("the content should not parse")
+    }
+
+    #[test]
+    fn simple_lines_have_a_hint_at_the_end() {
+        let exercises = parse("42 dec→bin 30 Start with the 32\n0x2A hex→bin\n0b101\n", false).unwrap();
+        assert_eq!(exercises.len(), 3);
+        assert_eq!(exercises[0].number, 42);
+        assert_eq!(exercises[0].time_limit, Some(30.0));
+        assert_eq!(exercises[0].hint.as_deref(), Some("Start with the 32"));
+        assert_eq!(exercises[1].number, 42);
+        assert_eq!(exercises[1].conversion.name(), "hex→bin");
+        assert_eq!(exercises[1].time_limit, None);
+        assert_eq!(exercises[2].number, 5);
+        assert_eq!(exercises[2].conversion.name(), "dec→bin");
+    }
+
+    #[test]
+    fn comments_and_empty_lines_are_skipped_but_counted() {
+        let errors = errors("# a comment\n\n1\nseven\n", false);
+        assert_eq!(errors, vec!["line 4: \"seven\" is no number from 0 to 255"]);
+    }
+
+    #[test]
+    fn quoted_csv_fields_keep_commas_and_quotes() {
+        let content = "number,conversion,time_limit,hint\n42,dec-bin,30,\"32 fits, what is \"\"left\"\"?\"\n7,bin2dec,-,\n";
+        let exercises = parse(content, true).unwrap();
+        assert_eq!(exercises.len(), 2);
+        assert_eq!(exercises[0].hint.as_deref(), Some("32 fits, what is \"left\"?"));
+        assert_eq!(exercises[1].conversion.name(), "bin→dec");
+        assert_eq!(exercises[1].hint, None);
+    }
+
+    #[test]
+    fn malformed_csv_is_reported() {
+        let errors = errors("1,dec-bin,10,\"never closed\n2,bin,10,a,b\n", true);
+        assert_eq!(errors, vec![
+            "line 1: a quote is never closed",
+            "line 2: 5 fields, but there are only 4",
+        ]);
+    }
+
+    #[test]
+    fn every_broken_line_is_reported_with_its_number() {
+        let errors = errors("256\n12 oct→bin\n3 dec→bin 0\n0x1G\n", false);
+        assert_eq!(errors, vec![
+            "line 1: \"256\" is no number from 0 to 255",
+            "line 2: \"oct→bin\" is no conversion, try dec→bin, bin→dec, dec→hex, hex→bin or bin→hex",
+            "line 3: \"0\" is no time limit, it has to be seconds above 0 or -",
+            "line 4: \"0x1G\" is no number from 0 to 255",
+        ]);
+    }
+
+    #[test]
+    fn a_header_is_only_skipped_before_the_first_exercise() {
+        let errors = errors("1\nnumber\n", true);
+        assert_eq!(errors, vec!["line 2: \"number\" is no number from 0 to 255"]);
+    }
+
+    #[test]
+    fn an_empty_set_is_an_error() {
+        assert_eq!(errors("# nothing\n", false), vec!["there is no exercise in it"]);
+    }
+
+    #[test]
+    fn a_line_reads_as_the_same_exercise_again() {
+        let line = "42 dec→hex 12.5 Two hex digits";
+        let exercise = parse(line, false).unwrap().remove(0);
+        assert_eq!(exercise.to_line(), line);
+    }
+}
@@ -1,6 +1,7 @@
-use sfml::window::Key;
 use sfml::window::mouse::{Button, Wheel};
 
+use crate::bindings::{Action, KeyChord};
+
 #[derive(Copy, Clone)] // Arbeit an den Compiler weitergeben
 pub enum GameTasks {
     NOP,
@@ -8,6 +9,16 @@ pub enum GameTasks {
     ClickPressed(Button, i32, i32),
     ClickReleased(Button, i32, i32),
     MouseWheelScrolled(Wheel, f32, i32, i32),
-    Typed(Key),
+    // menus highlight the entry under the mouse
+    MouseMoved(i32, i32),
+    // the window got switched away from (alt-tab) or back to
+    FocusLost,
+    FocusGained,
+    // a key, that has an action bound to it
+    Triggered(Action),
+    // any other key
+    Typed(KeyChord),
+    // the character the keyboard layout produced, used for digits and letters
+    TextEntered(char),
     Close,
 }
\ No newline at end of file
@@ -1,9 +1,20 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use sfml::window::Event;
 
+use crate::bindings::{Bindings, KeyChord, PadInput};
 use crate::game::game_tasks::GameTasks;
-use crate::game::game_tasks::GameTasks::Typed;
+use crate::game::game_tasks::GameTasks::{Triggered, Typed};
+use crate::game::joystick::{AXES, JoystickState, JoystickStates};
+
+// how far an axis has to be pushed, until it counts as pressed
+const AXIS_THRESHOLD: f32 = 50.0;
 
-pub struct Input {}
+pub struct Input {
+    bindings: Rc<RefCell<Bindings>>,
+    joysticks: JoystickStates,
+}
 
 impl Input {
     pub fn parse_input(&self, event: Event) -> Option<GameTasks> {
@@ -18,13 +29,165 @@ impl Input {
             Event::MouseWheelScrolled { wheel, delta, x, y } => Some(GameTasks::MouseWheelScrolled(wheel, delta, x, y)),
             Event::MouseButtonPressed { button, x, y } => Some(GameTasks::ClickPressed(button, x, y)),
             Event::MouseButtonReleased { button, x, y } => Some(GameTasks::ClickReleased(button, x, y)),
-            Event::KeyPressed { code, alt, ctrl, shift, system } =>
-                Some(Typed(code)),
+            Event::MouseMoved { x, y } => Some(GameTasks::MouseMoved(x, y)),
+            Event::LostFocus => Some(GameTasks::FocusLost),
+            Event::GainedFocus => Some(GameTasks::FocusGained),
+            Event::KeyPressed { code, alt, ctrl, shift, system } => {
+                let chord = KeyChord::new(code, alt, ctrl, shift, system);
+                match self.bindings.borrow().action_for(&chord) {
+                    Some(action) => Some(Triggered(action)),
+                    None => Some(Typed(chord)),
+                }
+            }
+            // control characters (like the one Ctrl+Q produces) are no text
+            Event::TextEntered { unicode } if !unicode.is_control() => Some(GameTasks::TextEntered(unicode)),
             _ => { Some(GameTasks::NOP) } //TODO: Fixen -> könnte schwer lös- und findbare Probleme bringen
         }
     }
+
+    /*
+    Joysticks are polled instead of using their events:
+    the new state gets compared with the last one and every button, that went down since then,
+    becomes a task. Because the state is handed in from outside,
+    a virtual joystick works just as well as a real one.
+    Joysticks, which get plugged in while playing, simply show up as connected in the next state.
+     */
+    pub fn update_joysticks(&mut self, joysticks: JoystickStates) -> Vec<GameTasks> {
+        let mut tasks = Vec::new();
+        for (id, (old, new)) in self.joysticks.iter().zip(joysticks.iter()).enumerate() {
+            if old.is_connected != new.is_connected {
+                println!("Joystick {} got {}", id, if new.is_connected { "connected" } else { "disconnected" });
+            }
+            if !new.is_connected {
+                continue;
+            }
+            for pad_input in pressed_since(old, new) {
+                if let Some(action) = self.bindings.borrow().action_for_pad(&pad_input) {
+                    tasks.push(Triggered(action));
+                }
+            }
+        }
+        self.joysticks = joysticks;
+        tasks
+    }
+}
+
+fn pressed_since(old: &JoystickState, new: &JoystickState) -> Vec<PadInput> {
+    let mut pressed = Vec::new();
+    for button in 0..u32::BITS {
+        if new.is_button_pressed(button) && !old.is_button_pressed(button) {
+            pressed.push(PadInput::Button(button));
+        }
+    }
+    for (i, axis) in AXES.iter().enumerate() {
+        if new.axes[i] > AXIS_THRESHOLD && old.axes[i] <= AXIS_THRESHOLD {
+            pressed.push(PadInput::AxisPositive(*axis));
+        }
+        if new.axes[i] < -AXIS_THRESHOLD && old.axes[i] >= -AXIS_THRESHOLD {
+            pressed.push(PadInput::AxisNegative(*axis));
+        }
+    }
+    pressed
+}
+
+pub fn new(bindings: Rc<RefCell<Bindings>>) -> Input {
+    Input {
+        bindings,
+        joysticks: [JoystickState::disconnected(); sfml::window::joystick::COUNT as usize],
+    }
 }
 
-pub fn new() -> Input {
-    Input {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings;
+    use crate::bindings::Action;
+
+    const POV_X: usize = 6;
+
+    fn input() -> Input {
+        new(Rc::new(RefCell::new(bindings::defaults())))
+    }
+
+    fn pad(buttons: u32, axes: &[(usize, f32)]) -> JoystickState {
+        let mut state = JoystickState { is_connected: true, ..JoystickState::disconnected() };
+        state.buttons = buttons;
+        for (i, position) in axes {
+            state.axes[*i] = *position;
+        }
+        state
+    }
+
+    // the states of all joysticks, with the given one at the id
+    fn with(id: usize, state: JoystickState) -> JoystickStates {
+        let mut states = [JoystickState::disconnected(); sfml::window::joystick::COUNT as usize];
+        states[id] = state;
+        states
+    }
+
+    fn actions(tasks: Vec<GameTasks>) -> Vec<Action> {
+        tasks.into_iter()
+            .map(|task| match task {
+                Triggered(action) => action,
+                _ => panic!("a joystick only triggers actions"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_button_triggers_once_while_held() {
+        let mut input = input();
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0b1, &[])))), vec![Action::InputOne]);
+        assert!(input.update_joysticks(with(0, pad(0b1, &[]))).is_empty());
+        assert!(input.update_joysticks(with(0, pad(0, &[]))).is_empty());
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0b1, &[])))), vec![Action::InputOne]);
+    }
+
+    #[test]
+    fn buttons_pressed_together_trigger_together() {
+        let mut input = input();
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0b100_0001, &[])))), vec![Action::InputOne, Action::Restart]);
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0b100_0011, &[])))), vec![Action::InputZero]);
+    }
+
+    #[test]
+    fn the_hat_counts_once_it_is_past_the_threshold() {
+        let mut input = input();
+        assert!(input.update_joysticks(with(0, pad(0, &[(POV_X, AXIS_THRESHOLD)]))).is_empty());
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0, &[(POV_X, 100.0)])))), vec![Action::CursorRight]);
+        assert!(input.update_joysticks(with(0, pad(0, &[(POV_X, 80.0)]))).is_empty());
+        // straight from one side to the other
+        assert_eq!(actions(input.update_joysticks(with(0, pad(0, &[(POV_X, -100.0)])))), vec![Action::CursorLeft]);
+    }
+
+    #[test]
+    fn unbound_buttons_trigger_nothing() {
+        let mut input = input();
+        assert!(input.update_joysticks(with(0, pad(1 << 20, &[(0, 100.0)]))).is_empty());
+    }
+
+    #[test]
+    fn a_disconnected_joystick_triggers_nothing() {
+        let mut input = input();
+        let unplugged = JoystickState { is_connected: false, ..pad(0b1, &[]) };
+        assert!(input.update_joysticks(with(0, unplugged)).is_empty());
+    }
+
+    #[test]
+    fn a_joystick_plugged_in_while_playing_works() {
+        let mut input = input();
+        assert!(input.update_joysticks(with(0, JoystickState::disconnected())).is_empty());
+        assert_eq!(actions(input.update_joysticks(with(3, pad(0b10, &[])))), vec![Action::InputZero]);
+        // pulled out and plugged in again with the button still held
+        assert!(input.update_joysticks(with(0, JoystickState::disconnected())).is_empty());
+        assert_eq!(actions(input.update_joysticks(with(3, pad(0b10, &[])))), vec![Action::InputZero]);
+    }
+
+    #[test]
+    fn every_joystick_counts() {
+        let mut input = input();
+        let mut states = with(0, pad(0b1, &[]));
+        states[1] = pad(0b1000_0000, &[]);
+        assert_eq!(actions(input.update_joysticks(states)), vec![Action::InputOne, Action::Pause]);
+    }
 }
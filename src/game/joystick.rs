@@ -0,0 +1,57 @@
+use sfml::window::joystick;
+use sfml::window::joystick::Axis;
+
+pub const AXES: [Axis; joystick::AXIS_COUNT as usize] = [
+    Axis::X, Axis::Y, Axis::Z, Axis::R, Axis::U, Axis::V, Axis::POV_X, Axis::POV_Y,
+];
+
+// Everything we want to know about a joystick at one point in time.
+// Input only ever looks at these snapshots, so a virtual joystick is nothing more
+// than a hand written JoystickState
+#[derive(Copy, Clone, PartialEq)]
+pub struct JoystickState {
+    pub is_connected: bool,
+    // one bit per button
+    pub buttons: u32,
+    // in the same order as AXES, from -100 to 100
+    pub axes: [f32; joystick::AXIS_COUNT as usize],
+}
+
+impl JoystickState {
+    pub fn disconnected() -> JoystickState {
+        JoystickState {
+            is_connected: false,
+            buttons: 0,
+            axes: [0.0; joystick::AXIS_COUNT as usize],
+        }
+    }
+
+    pub fn is_button_pressed(&self, button: u32) -> bool {
+        self.buttons >> button & 1 == 1
+    }
+}
+
+pub type JoystickStates = [JoystickState; joystick::COUNT as usize];
+
+// reads the state of every real joystick from SFML
+pub fn read_all() -> JoystickStates {
+    let mut states = [JoystickState::disconnected(); joystick::COUNT as usize];
+    for (id, state) in states.iter_mut().enumerate() {
+        let id = id as u32;
+        if !joystick::is_connected(id) {
+            continue;
+        }
+        state.is_connected = true;
+        for button in 0..joystick::button_count(id).min(joystick::BUTTON_COUNT) {
+            if joystick::is_button_pressed(id, button) {
+                state.buttons |= 1 << button;
+            }
+        }
+        for (i, axis) in AXES.iter().enumerate() {
+            if joystick::has_axis(id, *axis) {
+                state.axes[i] = joystick::axis_position(id, *axis);
+            }
+        }
+    }
+    states
+}
@@ -0,0 +1,180 @@
+use crate::arguments;
+use crate::config::Config;
+
+/*
+Where the numbers of a session come from. A source is written as a few words, separated by spaces:
+  "100-200"     every number from 100 to 200
+  "42"          just that number, several of them make a list ("1 2 4 8")
+  "pow2"        only powers of two
+  "bits=3"      only numbers with exactly three bits set
+  "even", "odd"
+Ranges and numbers add up, without any of them every number from 0 to 255 is in.
+The filters then take out what doesn't match, so "0-15 odd" is every odd number up to 15.
+The same text works on the command line (--numbers), in the profile (game.numbers) and in the menu.
+ */
+
+pub const ALL: &str = "0-255";
+
+#[derive(Copy, Clone, PartialEq)]
+enum Filter {
+    PowersOfTwo,
+    SetBits(u32),
+    Even,
+    Odd,
+}
+
+impl Filter {
+    fn matches(&self, number: u8) -> bool {
+        match self {
+            Filter::PowersOfTwo => number.is_power_of_two(),
+            Filter::SetBits(count) => number.count_ones() == *count,
+            Filter::Even => number % 2 == 0,
+            Filter::Odd => number % 2 == 1,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NumberSource {
+    // how it was written, so it can be shown and saved again
+    spec: String,
+    ranges: Vec<(u8, u8)>,
+    filters: Vec<Filter>,
+}
+
+impl NumberSource {
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    pub fn contains(&self, number: u8) -> bool {
+        let is_in_range = self.ranges.is_empty() || self.ranges.iter().any(|(min, max)| (*min..=*max).contains(&number));
+        is_in_range && self.filters.iter().all(|filter| filter.matches(number))
+    }
+
+    // every number the source can give, in ascending order
+    pub fn numbers(&self) -> Vec<u8> {
+        (0..=255).filter(|number| self.contains(*number)).collect()
+    }
+}
+
+fn parse_number(word: &str) -> Result<u8, String> {
+    word.parse().map_err(|_| format!("\"{}\" is no number from 0 to 255", word))
+}
+
+// tells what's wrong, if the text can't be read or no number is left
+pub fn parse(spec: &str) -> Result<NumberSource, String> {
+    let mut ranges = Vec::new();
+    let mut filters = Vec::new();
+    for word in spec.split_whitespace() {
+        let word = word.to_lowercase();
+        let filter = match word.as_str() {
+            "pow2" | "powers_of_two" => Some(Filter::PowersOfTwo),
+            "even" => Some(Filter::Even),
+            "odd" => Some(Filter::Odd),
+            _ => match word.strip_prefix("bits=") {
+                Some(count) => match count.parse() {
+                    Ok(count) if count <= 8 => Some(Filter::SetBits(count)),
+                    _ => return Err(format!("\"{}\" needs a count from 0 to 8", word)),
+                },
+                None => None,
+            },
+        };
+        if let Some(filter) = filter {
+            filters.push(filter);
+            continue;
+        }
+        let range = match word.split_once('-') {
+            Some((min, max)) => (parse_number(min)?, parse_number(max)?),
+            None => {
+                let number = parse_number(&word)?;
+                (number, number)
+            }
+        };
+        if range.0 > range.1 {
+            return Err(format!("\"{}\" goes backwards", word));
+        }
+        ranges.push(range);
+    }
+    let source = NumberSource { spec: spec.split_whitespace().collect::<Vec<_>>().join(" "), ranges, filters };
+    if source.numbers().is_empty() {
+        return Err(format!("\"{}\" leaves no number", spec));
+    }
+    Ok(source)
+}
+
+pub fn all() -> NumberSource {
+    NumberSource { spec: ALL.to_string(), ranges: Vec::new(), filters: Vec::new() }
+}
+
+// The command line wins over the profile, a source, that can't be read, falls back to every number.
+pub fn load(profile: &Config) -> NumberSource {
+    let spec = arguments::value_of("--numbers").or_else(|| profile.get("game.numbers").map(str::to_string));
+    match spec.as_deref().map(parse) {
+        Some(Ok(source)) => source,
+        Some(Err(error)) => {
+            println!("Playing every number, the number source is broken: {}", error);
+            all()
+        }
+        None => all(),
+    }
+}
+
+pub fn save(source: &NumberSource, config: &mut Config) {
+    config.set("game.numbers", source.spec());
+    if let Err(error) = config.save() {
+        println!("Could not save the number source: {}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(spec: &str) -> Vec<u8> {
+        parse(spec).unwrap_or_else(|error| panic!("{}: {}", spec, error)).numbers()
+    }
+
+    #[test]
+    fn a_range_has_both_ends() {
+        assert_eq!(numbers("100-200"), (100..=200).collect::<Vec<u8>>());
+        assert_eq!(numbers("7-7"), vec![7]);
+    }
+
+    #[test]
+    fn numbers_make_a_list() {
+        assert_eq!(numbers("8 1 4 2"), vec![1, 2, 4, 8]);
+        assert_eq!(numbers("1 0-3"), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn nothing_but_filters_is_every_number() {
+        assert_eq!(numbers("pow2"), vec![1, 2, 4, 8, 16, 32, 64, 128]);
+        assert_eq!(numbers("bits=3").len(), 56);
+        assert!(numbers("bits=3").iter().all(|number| number.count_ones() == 3));
+        assert_eq!(numbers("bits=0"), vec![0]);
+    }
+
+    #[test]
+    fn filters_take_out_of_the_ranges() {
+        assert_eq!(numbers("0-15 odd"), vec![1, 3, 5, 7, 9, 11, 13, 15]);
+        assert_eq!(numbers("0-15 bits=2 even"), vec![6, 10, 12]);
+        assert_eq!(numbers("POW2 16-255"), vec![16, 32, 64, 128]);
+    }
+
+    #[test]
+    fn the_spec_is_kept_tidy() {
+        assert_eq!(parse("  0-15   odd ").unwrap().spec(), "0-15 odd");
+        assert_eq!(all().numbers().len(), 256);
+    }
+
+    #[test]
+    fn broken_specs_tell_why() {
+        assert_eq!(parse("200-100").err().unwrap(), "\"200-100\" goes backwards");
+        assert_eq!(parse("bits=9").err().unwrap(), "\"bits=9\" needs a count from 0 to 8");
+        assert_eq!(parse("bits=x").err().unwrap(), "\"bits=x\" needs a count from 0 to 8");
+        assert_eq!(parse("0-300").err().unwrap(), "\"300\" is no number from 0 to 255");
+        assert_eq!(parse("ten").err().unwrap(), "\"ten\" is no number from 0 to 255");
+        assert_eq!(parse("pow2 bits=2").err().unwrap(), "\"pow2 bits=2\" leaves no number");
+    }
+}
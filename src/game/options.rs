@@ -0,0 +1,88 @@
+use crate::config::Config;
+use crate::game::session;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EntryMode {
+    // every bit has to be right, before the next one can be entered
+    Guided,
+    // the player moves a cursor over the slots and checks the whole answer at the end
+    Free,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum BitOrder {
+    // left to right, like reading the number
+    MsbFirst,
+    // right to left, the order in which dividing by 2 again and again produces the bits
+    LsbFirst,
+}
+
+// how a round is played, read from the "game." keys of the profile
+#[derive(Copy, Clone)]
+pub struct GameOptions {
+    pub entry_mode: EntryMode,
+    pub bit_order: BitOrder,
+    // the row of place values under the slots
+    pub show_place_values: bool,
+    // the sum of the bits entered so far ("128 + 0 + 32 = 160, 37 to go")
+    pub show_breakdown: bool,
+    // how many numbers make one session
+    pub rounds_per_session: u32,
+    // the difficulty follows how well the last rounds went
+    pub is_adaptive: bool,
+}
+
+impl GameOptions {
+    // high scores are only compared within the same mode, it's written like the config values
+    pub fn mode_key(&self) -> String {
+        let entry_mode = if self.entry_mode == EntryMode::Free { "free" } else { "guided" };
+        let bit_order = if self.bit_order == BitOrder::LsbFirst { "lsb_first" } else { "msb_first" };
+        format!("{}_{}", entry_mode, bit_order)
+    }
+}
+
+// how a mode key reads on screen
+pub fn mode_name(mode_key: &str) -> String {
+    if mode_key == session::EXERCISE_SET_MODE {
+        return "exercise set".to_string();
+    }
+    if mode_key == session::SPEEDRUN_MODE {
+        return "speedrun".to_string();
+    }
+    let (entry_mode, bit_order) = mode_key.split_once('_').unwrap_or((mode_key, ""));
+    let entry_mode = if entry_mode == "free" { "free entry" } else { "bit by bit" };
+    let bit_order = if bit_order == "lsb_first" { "right to left" } else { "left to right" };
+    format!("{}, {}", entry_mode, bit_order)
+}
+
+pub const DEFAULT_ROUNDS_PER_SESSION: u32 = 10;
+pub const MAX_ROUNDS_PER_SESSION: u32 = 50;
+
+pub fn load(config: &Config) -> GameOptions {
+    let entry_mode = if config.get("game.entry_mode") == Some("free") { EntryMode::Free } else { EntryMode::Guided };
+    let bit_order = if config.get("game.bit_order") == Some("lsb_first") { BitOrder::LsbFirst } else { BitOrder::MsbFirst };
+    GameOptions {
+        entry_mode,
+        bit_order,
+        show_place_values: config.get("game.show_place_values") != Some("false"),
+        show_breakdown: config.get("game.show_breakdown") != Some("false"),
+        rounds_per_session: config.get("game.rounds")
+            .and_then(|rounds| rounds.parse().ok())
+            .filter(|rounds| (1..=MAX_ROUNDS_PER_SESSION).contains(rounds))
+            .unwrap_or(DEFAULT_ROUNDS_PER_SESSION),
+        is_adaptive: config.get("game.adaptive") != Some("false"),
+    }
+}
+
+// remembers the options for the next start
+pub fn save(options: &GameOptions, config: &mut Config) {
+    config.set("game.entry_mode", if options.entry_mode == EntryMode::Free { "free" } else { "guided" });
+    config.set("game.bit_order", if options.bit_order == BitOrder::LsbFirst { "lsb_first" } else { "msb_first" });
+    config.set("game.show_place_values", if options.show_place_values { "true" } else { "false" });
+    config.set("game.show_breakdown", if options.show_breakdown { "true" } else { "false" });
+    config.set("game.rounds", &options.rounds_per_session.to_string());
+    config.set("game.adaptive", if options.is_adaptive { "true" } else { "false" });
+    if let Err(error) = config.save() {
+        println!("Could not save the game options: {}", error);
+    }
+}
@@ -0,0 +1,524 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use sfml::system::Vector2u;
+use sfml::window::mouse::{Button, Wheel};
+
+use crate::arguments;
+use crate::bindings::{Action, KeyChord};
+use crate::game;
+use crate::game::difficulty;
+use crate::game::difficulty::Level;
+use crate::game::exercise;
+use crate::game::exercise::Exercise;
+use crate::game::game_tasks::GameTasks;
+use crate::game::options::{BitOrder, EntryMode, GameOptions};
+use crate::game::round_stats::RoundStats;
+use crate::game::Game;
+use crate::resources::Resources;
+use crate::user_data;
+
+/*
+A recording holds everything the rounds of a session depend on, so they can be played again exactly:
+the options, the exercise and the level of every round, every task the game got and the length of every frame.
+It's plain text, one entry per line, so it can be attached to a bug report and read by a human:
+    mr_binaer recording 1
+    seed 8254091712                   picked the numbers of the session (see --seed)
+    options guided msb_first true true    entry mode, bit order, place values, sum of the bits
+    round 3 42 dec→bin 30 Start with the 32    the level, then the exercise as in an exercise set
+    window 800 600
+    task 12 0.20 click_pressed left 310 240    frame of the round, seconds of the session, the task
+    frame 0.016667                    one frame, that took that many seconds
+    end 12.5 Number 42 (dec→bin): solved, ...    seconds and summary of the round, to compare a replay with
+The tasks are: click_pressed/click_released <button> <x> <y>, wheel <wheel> <delta> <x> <y>, mouse_moved <x> <y>,
+focus_lost, focus_gained, action <action>, key <key chord>, text <character code>, close and nop.
+The version goes up, whenever a line changes its meaning.
+ */
+
+pub const VERSION: u32 = 1;
+
+const BUTTONS: [(Button, &str); 5] = [
+    (Button::LEFT, "left"),
+    (Button::RIGHT, "right"),
+    (Button::MIDDLE, "middle"),
+    (Button::X_BUTTON_1, "x1"),
+    (Button::X_BUTTON_2, "x2"),
+];
+
+fn button_name(button: Button) -> &'static str {
+    BUTTONS.iter().find(|(other, _)| *other == button).map_or("left", |(_, name)| name)
+}
+
+fn wheel_name(wheel: Wheel) -> &'static str {
+    match wheel {
+        Wheel::Vertical => "vertical",
+        _ => "horizontal",
+    }
+}
+
+fn task_to_text(task: &GameTasks) -> String {
+    match task {
+        GameTasks::NOP => "nop".to_string(),
+        GameTasks::ClickPressed(button, x, y) => format!("click_pressed {} {} {}", button_name(*button), x, y),
+        GameTasks::ClickReleased(button, x, y) => format!("click_released {} {} {}", button_name(*button), x, y),
+        GameTasks::MouseWheelScrolled(wheel, delta, x, y) => format!("wheel {} {} {} {}", wheel_name(*wheel), delta, x, y),
+        GameTasks::MouseMoved(x, y) => format!("mouse_moved {} {}", x, y),
+        GameTasks::FocusLost => "focus_lost".to_string(),
+        GameTasks::FocusGained => "focus_gained".to_string(),
+        GameTasks::Triggered(action) => format!("action {}", action.name()),
+        GameTasks::Typed(chord) => format!("key {}", chord.to_text()),
+        GameTasks::TextEntered(character) => format!("text {}", *character as u32),
+        GameTasks::Close => "close".to_string(),
+    }
+}
+
+fn parse_task(text: &str) -> Option<GameTasks> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let number = |index: usize| words.get(index).and_then(|word| word.parse::<i32>().ok());
+    let button = |index: usize| words.get(index).and_then(|word| BUTTONS.iter().find(|(_, name)| name == word)).map(|(button, _)| *button);
+    Some(match *words.first()? {
+        "nop" => GameTasks::NOP,
+        "click_pressed" => GameTasks::ClickPressed(button(1)?, number(2)?, number(3)?),
+        "click_released" => GameTasks::ClickReleased(button(1)?, number(2)?, number(3)?),
+        "wheel" => {
+            let wheel = if *words.get(1)? == "vertical" { Wheel::Vertical } else { Wheel::Horizontal };
+            GameTasks::MouseWheelScrolled(wheel, words.get(2)?.parse().ok()?, number(3)?, number(4)?)
+        }
+        "mouse_moved" => GameTasks::MouseMoved(number(1)?, number(2)?),
+        "focus_lost" => GameTasks::FocusLost,
+        "focus_gained" => GameTasks::FocusGained,
+        "action" => {
+            let name = *words.get(1)?;
+            GameTasks::Triggered(Action::ALL.into_iter().find(|action| action.name() == name)?)
+        }
+        "key" => GameTasks::Typed(KeyChord::parse(words.get(1)?)?),
+        "text" => GameTasks::TextEntered(char::from_u32(words.get(1)?.parse().ok()?)?),
+        "close" => GameTasks::Close,
+        _ => return None,
+    })
+}
+
+fn options_to_text(options: &GameOptions) -> String {
+    format!("{} {} {} {}",
+            if options.entry_mode == EntryMode::Free { "free" } else { "guided" },
+            if options.bit_order == BitOrder::LsbFirst { "lsb_first" } else { "msb_first" },
+            options.show_place_values, options.show_breakdown)
+}
+
+// the options of the player, that replays, only keep what doesn't change a round
+fn parse_options(text: &str, options: GameOptions) -> Option<GameOptions> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    Some(GameOptions {
+        entry_mode: if *words.first()? == "free" { EntryMode::Free } else { EntryMode::Guided },
+        bit_order: if *words.get(1)? == "lsb_first" { BitOrder::LsbFirst } else { BitOrder::MsbFirst },
+        show_place_values: words.get(2)?.parse().ok()?,
+        show_breakdown: words.get(3)?.parse().ok()?,
+        ..options
+    })
+}
+
+// writes the lines while a session is played
+pub struct Recorder {
+    lines: Vec<String>,
+    // the frame of the round and the seconds of the session
+    frame: u32,
+    seconds: f32,
+    window_size: Option<Vector2u>,
+}
+
+impl Recorder {
+    pub fn start_round(&mut self, exercise: &Exercise, level: Level) {
+        self.lines.push(format!("round {} {}", level.number, exercise.to_line()));
+        self.frame = 0;
+    }
+
+    pub fn task(&mut self, task: &GameTasks, window_size: Vector2u) {
+        if self.window_size != Some(window_size) {
+            self.window_size = Some(window_size);
+            self.lines.push(format!("window {} {}", window_size.x, window_size.y));
+        }
+        self.lines.push(format!("task {} {:.2} {}", self.frame, self.seconds, task_to_text(task)));
+    }
+
+    pub fn frame(&mut self, frame_seconds: f32) {
+        // Display writes the shortest text, that reads as the very same f32 again
+        self.lines.push(format!("frame {}", frame_seconds));
+        self.frame += 1;
+        self.seconds += frame_seconds;
+    }
+
+    pub fn end_round(&mut self, stats: &RoundStats) {
+        self.lines.push(format!("end {} {}", stats.seconds, stats.summary()));
+    }
+
+    fn text(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    pub fn save(&self, path: &Path) {
+        match user_data::write_atomically(path, &self.text()) {
+            Ok(()) => println!("Recorded the session to {}", path.display()),
+            Err(error) => println!("Could not record the session: {}", error),
+        }
+    }
+}
+
+pub fn recorder(seed: u64, options: &GameOptions) -> Recorder {
+    Recorder {
+        lines: vec![
+            format!("mr_binaer recording {}", VERSION),
+            format!("seed {}", seed),
+            format!("options {}", options_to_text(options)),
+        ],
+        frame: 0,
+        seconds: 0.0,
+        window_size: None,
+    }
+}
+
+// --record <file> puts the recording there, without it the last session is kept for bug reports
+pub fn path_from_arguments() -> PathBuf {
+    arguments::value_of("--record").map_or_else(|| user_data::path("last_session.rec"), PathBuf::from)
+}
+
+// what the game got between two frames, in the order it got it
+#[derive(Copy, Clone)]
+pub enum Step {
+    Window(Vector2u),
+    Task(GameTasks),
+    Frame(f32),
+}
+
+pub struct RecordedRound {
+    pub exercise: Exercise,
+    pub level: Level,
+    pub steps: Vec<Step>,
+    // seconds and summary at the end, missing if the game was closed in the middle of the round
+    pub end: Option<(f32, String)>,
+}
+
+pub struct Recording {
+    pub seed: u64,
+    pub options: GameOptions,
+    pub rounds: Vec<RecordedRound>,
+}
+
+fn parse_round(rest: &str) -> Result<RecordedRound, String> {
+    let (level, exercise) = rest.split_once(' ').ok_or("a round needs a level and an exercise")?;
+    let level: usize = level.parse().map_err(|_| format!("\"{}\" is no level", level))?;
+    if level > difficulty::LEVELS.len() {
+        return Err(format!("there is no level {}", level));
+    }
+    let exercise = exercise::parse(exercise, false).map_err(|errors| errors.join(", "))?.remove(0);
+    Ok(RecordedRound { exercise, level: difficulty::by_number(level), steps: Vec::new(), end: None })
+}
+
+fn parse_line(line: &str, recording: &mut Recording) -> Result<(), String> {
+    let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+    if kind == "round" {
+        recording.rounds.push(parse_round(rest)?);
+        return Ok(());
+    }
+    let round = recording.rounds.last_mut();
+    match (kind, round) {
+        ("seed", _) => recording.seed = rest.parse().map_err(|_| format!("\"{}\" is no seed", rest))?,
+        ("options", _) => recording.options = parse_options(rest, recording.options).ok_or("the options can't be read")?,
+        ("window", Some(round)) => {
+            let (width, height) = rest.split_once(' ').ok_or("a window needs a width and a height")?;
+            let size = (width.parse(), height.parse());
+            let (Ok(width), Ok(height)) = size else { return Err(format!("\"{}\" is no window size", rest)) };
+            round.steps.push(Step::Window(Vector2u::new(width, height)));
+        }
+        // the frame and the seconds are only there to be read by humans
+        ("task", Some(round)) => {
+            let task = rest.splitn(3, ' ').nth(2).and_then(parse_task).ok_or_else(|| format!("\"{}\" is no task", rest))?;
+            round.steps.push(Step::Task(task));
+        }
+        ("frame", Some(round)) => round.steps.push(Step::Frame(rest.parse().map_err(|_| format!("\"{}\" is no frame length", rest))?)),
+        ("end", Some(round)) => {
+            let (seconds, summary) = rest.split_once(' ').ok_or("an end needs the seconds and a summary")?;
+            round.end = Some((seconds.parse().map_err(|_| format!("\"{}\" are no seconds", seconds))?, summary.to_string()));
+        }
+        ("window" | "task" | "frame" | "end", None) => return Err(format!("\"{}\" comes before the first round", kind)),
+        _ => return Err(format!("\"{}\" is unknown", kind)),
+    }
+    Ok(())
+}
+
+// like an exercise set, every line, that can't be read, is reported with its line number
+pub fn load(path: &str, options: GameOptions) -> Result<Recording, Vec<String>> {
+    let content = fs::read_to_string(path).map_err(|error| vec![format!("could not read {}: {}", path, error)])?;
+    parse(&content, path, options)
+}
+
+// the name is the one of the file, for the errors
+fn parse(content: &str, name: &str, options: GameOptions) -> Result<Recording, Vec<String>> {
+    let mut lines = content.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header == format!("mr_binaer recording {}", VERSION) => {}
+        Some((_, header)) if header.starts_with("mr_binaer recording ") => return Err(vec![format!("{} is recorded by another version of the game", name)]),
+        _ => return Err(vec![format!("{} is no recording", name)]),
+    }
+    let mut recording = Recording { seed: 0, options, rounds: Vec::new() };
+    let mut errors = Vec::new();
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Err(error) = parse_line(line, &mut recording) {
+            errors.push(format!("line {}: {}", i + 1, error));
+        }
+    }
+    if recording.rounds.is_empty() && errors.is_empty() {
+        errors.push("there is no round in it".to_string());
+    }
+    if errors.is_empty() { Ok(recording) } else { Err(errors) }
+}
+
+// Plays the rounds of a recording again, one frame at a time, with the very same tasks and frame lengths.
+// The replay scene shows it, the ghost of a speedrun runs it next to the player.
+pub struct Replayer {
+    recording: Recording,
+    // the round played at the moment, it's the count of the rounds once all are played
+    round: usize,
+    step: usize,
+    window_size: Vector2u,
+    game: Game,
+    // the seconds of the rounds before the current one
+    seconds_before: f32,
+    is_muted: bool,
+    resources: Rc<RefCell<Resources>>,
+}
+
+impl Replayer {
+    pub fn game(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn recorded_round(&self) -> Option<&RecordedRound> {
+        self.recording.rounds.get(self.round)
+    }
+
+    // the seconds of the session so far, like the ones of the rounds add up
+    pub fn seconds(&self) -> f32 {
+        if self.round < self.recording.rounds.len() { self.seconds_before + self.game.seconds() } else { self.seconds_before }
+    }
+
+    // the replayed rounds make no sound (like the ghost, that plays alongside the player)
+    pub fn mute(&mut self) {
+        self.is_muted = true;
+        self.game.mute();
+    }
+
+    // everything up to the next frame of the recording, then that frame, false once the round is over
+    pub fn play_frame(&mut self) -> bool {
+        let Some(round) = self.recording.rounds.get(self.round) else { return false };
+        while let Some(step) = round.steps.get(self.step).copied() {
+            self.step += 1;
+            match step {
+                Step::Window(size) => self.window_size = size,
+                Step::Task(task) => self.game.handle_task(task, self.window_size),
+                Step::Frame(frame_seconds) => {
+                    self.game.update(frame_seconds);
+                    break;
+                }
+            }
+        }
+        !self.game.is_stopped() && self.step < round.steps.len()
+    }
+
+    // false, when the round was the last one
+    pub fn next_round(&mut self) -> bool {
+        if self.round >= self.recording.rounds.len() {
+            return false;
+        }
+        self.seconds_before += self.game.seconds();
+        self.round += 1;
+        self.step = 0;
+        match self.recording.rounds.get(self.round) {
+            Some(round) => {
+                self.game = new_round(round, self.recording.options, self.is_muted, &self.resources);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn new_round(round: &RecordedRound, options: GameOptions, is_muted: bool, resources: &Rc<RefCell<Resources>>) -> Game {
+    let mut game = game::new(&round.exercise, round.level, 120, options, Rc::clone(resources));
+    if is_muted {
+        game.mute();
+    }
+    game
+}
+
+// the recording needs at least one round (like load makes sure)
+pub fn replayer(recording: Recording, window_size: Vector2u, resources: Rc<RefCell<Resources>>) -> Replayer {
+    Replayer {
+        game: new_round(&recording.rounds[0], recording.options, false, &resources),
+        recording,
+        round: 0,
+        step: 0,
+        window_size,
+        seconds_before: 0.0,
+        is_muted: false,
+        resources,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sfml::window::Key;
+
+    use super::*;
+    use crate::resources;
+
+    const ANIMATION_FRAMES: i32 = 120;
+    const FRAME: f32 = 1.0 / 60.0;
+
+    fn options() -> GameOptions {
+        GameOptions {
+            entry_mode: EntryMode::Guided,
+            bit_order: BitOrder::MsbFirst,
+            show_place_values: true,
+            show_breakdown: false,
+            rounds_per_session: 2,
+            is_adaptive: false,
+        }
+    }
+
+    fn round(exercise: &Exercise, level: Level, options: GameOptions) -> Game {
+        game::new(exercise, level, ANIMATION_FRAMES, options, Rc::new(RefCell::new(resources::new())))
+    }
+
+    // plays a round like the game scene does: every task and frame goes into the recorder and the game
+    struct Player {
+        recorder: Recorder,
+        game: Game,
+        window_size: Vector2u,
+        // of the rounds played to the end
+        seconds: f32,
+    }
+
+    impl Player {
+        fn task(&mut self, task: GameTasks) {
+            self.recorder.task(&task, self.window_size);
+            self.game.handle_task(task, self.window_size);
+        }
+
+        fn frames(&mut self, count: usize) {
+            for _ in 0..count {
+                if self.game.is_stopped() {
+                    return;
+                }
+                self.recorder.frame(FRAME);
+                self.game.update(FRAME);
+            }
+        }
+
+        fn start_round(&mut self, exercise: &Exercise) {
+            self.recorder.start_round(exercise, difficulty::FIXED);
+            self.game = round(exercise, difficulty::FIXED, options());
+        }
+
+        fn end_round(&mut self) -> String {
+            let stats = self.game.stats();
+            self.recorder.end_round(&stats);
+            self.seconds += stats.seconds;
+            format!("{} {}", stats.seconds, stats.summary())
+        }
+    }
+
+    // the summaries of the rounds, once played and written down, then replayed from the text
+    fn play_and_replay() -> (Vec<String>, Vec<String>) {
+        let first = exercise::parse("5 dec→bin", false).unwrap().remove(0);
+        let second = exercise::parse("0x2A hex→bin", false).unwrap().remove(0);
+        let mut player = Player {
+            recorder: recorder(77, &options()),
+            game: round(&first, difficulty::FIXED, options()),
+            window_size: Vector2u::new(800, 600),
+            seconds: 0.0,
+        };
+        player.recorder.start_round(&first, difficulty::FIXED);
+        // 0000 0101, with a wrong bit and a hint on the way
+        for (i, action) in [Action::InputZero, Action::InputOne, Action::InputZero, Action::InputZero, Action::InputZero,
+                            Action::InputZero, Action::Hint, Action::InputOne, Action::InputZero, Action::InputOne].into_iter().enumerate() {
+            player.frames(i % 3 + 1);
+            player.task(GameTasks::Triggered(action));
+        }
+        player.window_size = Vector2u::new(1024, 768);
+        player.frames(ANIMATION_FRAMES as usize + 5);
+        // any key turns the fir tree back and ends the round
+        player.task(GameTasks::Typed(KeyChord::plain(Key::A)));
+        player.frames(ANIMATION_FRAMES as usize + 5);
+        assert!(player.game.is_stopped());
+        let mut played = vec![player.end_round()];
+
+        player.start_round(&second);
+        player.frames(10);
+        player.task(GameTasks::TextEntered('1'));
+        player.frames(4);
+        player.task(GameTasks::Triggered(Action::Quit));
+        assert!(player.game.is_stopped());
+        played.push(player.end_round());
+
+        let recording = parse(&player.recorder.text(), "the test", options()).unwrap_or_else(|errors| panic!("{}", errors.join("\n")));
+        assert_eq!(recording.seed, 77);
+        let mut replayer = replayer(recording, Vector2u::new(800, 600), Rc::new(RefCell::new(resources::new())));
+        replayer.mute();
+        let mut replayed = Vec::new();
+        loop {
+            while replayer.play_frame() {}
+            let stats = replayer.game().stats();
+            let (seconds, summary) = replayer.recorded_round().and_then(|recorded| recorded.end.clone()).expect("every round got to its end");
+            assert_eq!(format!("{} {}", seconds, summary), format!("{} {}", stats.seconds, stats.summary()));
+            replayed.push(format!("{} {}", stats.seconds, stats.summary()));
+            if !replayer.next_round() {
+                break;
+            }
+        }
+        assert_eq!(replayer.round(), 2);
+        assert!(!replayer.play_frame());
+        assert_eq!(replayer.seconds(), player.seconds);
+        (played, replayed)
+    }
+
+    #[test]
+    fn a_replay_ends_like_the_recorded_session() {
+        let (played, replayed) = play_and_replay();
+        assert_eq!(played.len(), 2);
+        assert!(played[0].contains(": solved,"), "{}", played[0]);
+        assert_eq!(played, replayed);
+    }
+
+    #[test]
+    fn broken_lines_are_reported_with_their_number() {
+        let content = "mr_binaer recording 1\nseed 1\nframe 0.1\nround 0 5 dec→bin\ntask 0 0.00 jump\nframe fast\n";
+        let errors = parse(content, "the test", options()).err().unwrap();
+        assert_eq!(errors, vec![
+            "line 3: \"frame\" comes before the first round",
+            "line 5: \"0 0.00 jump\" is no task",
+            "line 6: \"fast\" is no frame length",
+        ]);
+        assert_eq!(parse("mr_binaer recording 0\n", "old.rec", options()).err().unwrap(), vec!["old.rec is recorded by another version of the game"]);
+    }
+
+    #[test]
+    fn every_task_reads_as_itself_again() {
+        let tasks = [
+            "click_pressed right 10 -4", "click_released x1 0 0", "wheel vertical -1.5 3 4", "mouse_moved 5 6",
+            "focus_lost", "focus_gained", "action submit", "key Ctrl+Q", "text 97", "close", "nop",
+        ];
+        for text in tasks {
+            let task = parse_task(text).unwrap_or_else(|| panic!("{} is no task", text));
+            assert_eq!(task_to_text(&task), text);
+        }
+    }
+}
@@ -1,10 +1,15 @@
-use std::ops::{Add, Deref};
+use std::cell::RefCell;
+use std::ops::Add;
+use std::rc::Rc;
 
-use sfml::graphics::{Color, Font, RenderTarget, RenderWindow, Text, Transformable, Vertex, VertexArray};
-use sfml::SfBox;
-use sfml::system::{Vector2, Vector2f};
+use sfml::graphics::{Color, FloatRect, RectangleShape, RenderTarget, RenderWindow, Shape, Sprite, Transformable, Vertex, VertexArray};
+use sfml::system::{Vector2, Vector2f, Vector2u};
 
+use crate::game::board::Board;
+use crate::game::options::GameOptions;
 use crate::game::snowman_states::SnowmanStates;
+use crate::resources::Resources;
+use crate::ui;
 
 const SNOWMAN: [Vector2<f32>; 17] = [
     Vector2f::new(5.0, 8.0),
@@ -59,88 +64,255 @@ const HAT: [Vector2<f32>; 7] = [
     Vector2f::new(2.0, 2.0), Vector2f::new(2.0, 0.0), Vector2f::new(3.0, 0.0),
     Vector2f::new(0.0, 0.0)];
 
+// how much of the ghost shines through, out of 255
+const GHOST_ALPHA: u8 = 70;
+// the ghost snowman stands a bit to the right of the real one
+const GHOST_OFFSET: f32 = 60.0;
+
+// how long a slot flashes and shakes after a wrong bit
+const WRONG_FLASH_FRAMES: i32 = 40;
+const WRONG_HISTORY_LENGTH: usize = 5;
+
 pub struct Renderer {
     snowman_pos: Vector2f,
     snowman_scale: Vector2f,
     snowman_idle_amplifier: f32,
     animation_duration: i32,
+    options: GameOptions,
     hat_left: Vector2f,
     hat_right: Vector2f,
-    font: SfBox<Font>,
+    // the hat falls one step per frame, drawing the same frame again (while paused) doesn't move it
+    hat_frame: Option<i32>,
+    resources: Rc<RefCell<Resources>>,
 }
 
 impl Renderer {
-    pub(crate) fn render(&mut self, window: &mut RenderWindow, player_input: &Vec<u8>, origin: &String, current_frame: i32, snowman_state: SnowmanStates) {
-        window.clear(Color::WHITE);
-        // draw origin (the number to be converted)
-        let mut text_origin = Text::new(origin, self.font.deref(), 200);
-        text_origin.set_fill_color(Color::BLACK);
-        text_origin.set_position(Vector2f::new((window.size().x / 2) as f32 - text_origin.global_bounds().width / 2 as f32, 25.0));
+    // a line in the top right corner, like the level and the time left
+    pub(crate) fn draw_status(&self, window: &mut RenderWindow, status: &str) {
+        let resources = self.resources.borrow();
+        let style = ui::style(&resources);
+        style.draw_text(window, status, 18, style.theme.text, Vector2f::new(window.size().x as f32 - 190.0, 10.0));
+    }
 
-        let text_input = Vec::<Text>::with_capacity(8);
-        for i in 0..(text_input.capacity()) { // ranges seem to behave strangely
-            let wrapped = &player_input.get(i);
-            let mut text = "_".to_string();
-            if wrapped.is_some() {
-                text = player_input.get(i).unwrap().to_string();
-            }
-            let mut text_input_build = Text::new(text.deref(), self.font.deref(), 25);
-            text_input_build.set_fill_color(Color::BLACK);
-            text_input_build.set_position(Vector2f::new(window.size().x as f32 / 3.25 + (70 * i) as f32, 500.0));
-            window.draw(&text_input_build);
-
-            let mut help_text = Text::new(format!("{}", (2_i32.pow((7 - i) as u32))).deref(), self.font.deref(), 25);
-            help_text.set_fill_color(Color::BLACK);
-            help_text.set_position(Vector2f::new(window.size().x as f32 / 3.25 + (70.0 * i as f32), 550.0));
-            window.draw(&help_text);
+    // a line of its own, under the number (like the hint of an exercise)
+    pub(crate) fn draw_note(&self, window: &mut RenderWindow, note: &str) {
+        let resources = self.resources.borrow();
+        let style = ui::style(&resources);
+        let mut text = style.coloured_text(note, 20, style.theme.highlight);
+        text.set_position(Vector2f::new((window.size().x as f32 - text.global_bounds().width) / 2.0, 250.0));
+        window.draw(&text);
+    }
+
+    // The personal best as a translucent snowman, in the very state its replay is in,
+    // and translucent boxes over the slots it has right (all of them, once it is at a later number).
+    pub(crate) fn draw_ghost(&self, window: &mut RenderWindow, board: &Board, current_frame: i32, snowman_state: SnowmanStates, numbers_ahead: i32) {
+        let resources = self.resources.borrow();
+        let style = ui::style(&resources);
+        let with_alpha = |colour: Color| Color::rgba(colour.red(), colour.green(), colour.blue(), GHOST_ALPHA);
+
+        let (shape, scale) = self.snowman_shape(&resources, current_frame, snowman_state);
+        let mut drawing = VertexArray::new(sfml::graphics::PrimitiveType::LINE_STRIP, shape.len());
+        for point in &shape {
+            drawing.append(&Vertex::new(
+                Vector2f::new(self.snowman_pos.x + GHOST_OFFSET + point.x * scale.x, self.snowman_pos.y - point.y * scale.y),
+                with_alpha(style.theme.text), Vector2f::new(0.0, 0.0)));
         }
+        window.draw(&drawing);
 
-        window.draw(&text_origin);
+        for slot in 0..board.len() {
+            let is_right = match numbers_ahead {
+                0 => board.slot(slot) == Some(board.expected(slot)),
+                ahead => ahead > 0,
+            };
+            if !is_right {
+                continue;
+            }
+            let bounds = slot_bounds(window.size(), slot);
+            let mut filled = RectangleShape::with_size(Vector2f::new(bounds.width, bounds.height));
+            filled.set_position(Vector2f::new(bounds.left, bounds.top));
+            filled.set_fill_color(with_alpha(style.theme.highlight));
+            window.draw(&filled);
+        }
+    }
 
+    // the points of the snowman (or what it turned into) at the frame, and how much they get stretched
+    fn snowman_shape(&self, resources: &Resources, current_frame: i32, snowman_state: SnowmanStates) -> (Vec<Vector2f>, Vector2f) {
+        // shapes from disk replace the built-in ones, as long as they have the same amount of points
+        let snowman_shape = shape_or(resources, "snowman", SNOWMAN);
+        let christmas_tree_shape = shape_or(resources, "christmas_tree", CHRISTMAS_TREE);
         let mut snowman_scale = Vector2f::new(self.snowman_scale.x, self.snowman_scale.y + f32::sin(current_frame as f32 / 150.0) * self.snowman_idle_amplifier);
 
-        // draw the snowman, we all love
         let snowman =
             match snowman_state {
-                SnowmanStates::Idle => get_snowman(),
+                SnowmanStates::Idle => snowman_shape.to_vec(),
                 SnowmanStates::Melting(animation_start) => {
                     // hard overwriting snowman_scale
                     snowman_scale = Vector2f::new(snowman_scale.x, snowman_scale.y - snowman_scale.y * (current_frame - animation_start) as f32 / self.animation_duration as f32);
-                    get_snowman()
+                    snowman_shape.to_vec()
                 },
                 SnowmanStates::Melted => {
                     // hard overwriting snowman_scale
                     snowman_scale = Vector2f::new(snowman_scale.x, 0.0);
-                    get_snowman()
+                    snowman_shape.to_vec()
                 },
                 SnowmanStates::MorphingIntoAFirTree(animation_start) => {
                     snowman_scale = Vector2f::new(snowman_scale.x, snowman_scale.y + (((current_frame as f32 / 64.0).sin() * 7.0) * ((current_frame as f32 / 64.0).tan() + 1.0 * 3.0) + (((current_frame as f32).sin()) * 0.05)) / self.animation_duration as f32 * (current_frame - animation_start) as f32);
-                    morph_into_christmas_tree(current_frame - animation_start, self.animation_duration)
+                    morph_into_christmas_tree(snowman_shape, christmas_tree_shape, current_frame - animation_start, self.animation_duration)
                 },
                 SnowmanStates::MorphingFromAFirTree(animation_start) => {
                     snowman_scale = Vector2f::new(snowman_scale.x, snowman_scale.y + (((current_frame as f32 / 64.0).sin() * 7.0) * ((current_frame as f32 / 64.0).tan() + 1.0 * 3.0) + (((current_frame as f32).sin()) * 0.05)) / self.animation_duration as f32 * (animation_start - current_frame - animation_start) as f32);
-                    morph_from_christmas_tree(current_frame - animation_start, self.animation_duration)
+                    morph_from_christmas_tree(snowman_shape, christmas_tree_shape, current_frame - animation_start, self.animation_duration)
                 },
                 SnowmanStates::IsFirTree() => {
                     snowman_scale = Vector2f::new(snowman_scale.x, snowman_scale.y + ((current_frame as f32 / 64.0).sin() * 7.0) * ((current_frame as f32 / 64.0).tan() + 1.0 * 3.0) + (((current_frame as f32).sin()) * 0.05));
-                    get_christmas_tree()
+                    christmas_tree_shape.to_vec()
                 },
                 _ => {
                     println!("No rendering is defined for snowman_state");
                     EMPTY.to_vec()
                 },
             };
+        (snowman, snowman_scale)
+    }
+
+    pub(crate) fn render(&mut self, window: &mut RenderWindow, board: &Board, origin: &String, current_frame: i32, snowman_state: SnowmanStates) {
+        let resources = Rc::clone(&self.resources);
+        let resources = resources.borrow();
+        let style = ui::style(&resources);
+        let hat_shape = shape_or(&resources, "hat", HAT);
+
+        let theme = style.theme;
+
+        window.clear(theme.background);
+        if let Some(texture) = resources.texture("background") {
+            window.draw(&Sprite::with_texture(texture));
+        }
+        // draw origin (the number to be converted), a long binary number has to be smaller to fit
+        let origin_size = (640 / origin.chars().count().max(1) as u32).min(200);
+        let mut text_origin = style.text(origin, origin_size);
+        text_origin.set_position(Vector2f::new((window.size().x / 2) as f32 - text_origin.global_bounds().width / 2 as f32, 25.0));
+
+        // a wrong bit makes its slot flash and shake for a moment
+        let flash = board.last_wrong_attempt()
+            .filter(|attempt| current_frame - attempt.frame < WRONG_FLASH_FRAMES);
+
+        for i in 0..board.len() {
+            let mut bounds = slot_bounds(window.size(), i);
+            let mut flashing_value = None;
+            if let Some(attempt) = flash.filter(|attempt| attempt.slot == i) {
+                let progress = (current_frame - attempt.frame) as f32 / WRONG_FLASH_FRAMES as f32;
+                bounds.left += f32::sin((current_frame - attempt.frame) as f32 * 1.5) * 6.0 * (1.0 - progress);
+                flashing_value = Some(attempt.value);
+            }
+
+            // a light box, so everybody sees the slots can be clicked
+            if flashing_value.is_some() {
+                style.draw_frame(window, bounds, theme.wrong, 2.0);
+            } else if board.cursor() == Some(i) {
+                style.draw_frame(window, bounds, theme.highlight, 2.0);
+            } else {
+                style.draw_frame(window, bounds, theme.outline, 1.0);
+            }
+
+            // an empty slot shows the rejected bit while it flashes
+            let text = match (board.slot(i), flashing_value) {
+                (Some(digit), _) => format!("{:X}", digit),
+                (None, Some(wrong)) => format!("{:X}", wrong),
+                (None, None) => "_".to_string(),
+            };
+            // bits, that are known to be right or wrong, show it
+            let colour = match board.feedback(i) {
+                _ if flashing_value.is_some() => theme.wrong,
+                Some(true) => theme.correct,
+                Some(false) => theme.wrong,
+                None => theme.text,
+            };
+            style.draw_text(window, &text, 25, colour, Vector2f::new(bounds.left + 15.0, 500.0));
+
+            if self.options.show_place_values {
+                // the first hint points at the biggest power of two, that still fits
+                let is_hinted = board.hint().is_some_and(|hint| hint.largest_power == Some(board.place_value(i)));
+                style.draw_text(window, &board.place_value(i).to_string(), 25, if is_hinted { theme.highlight } else { theme.text },
+                                Vector2f::new(window.size().x as f32 / 3.25 + (70.0 * i as f32), 550.0));
+            }
+        }
+
+        if self.options.show_breakdown {
+            // adds up what the entered bits are worth, so nobody has to do it in their head
+            let terms: Vec<u32> = (0..board.len())
+                .filter_map(|slot| board.slot(slot).map(|bit| bit as u32 * board.place_value(slot)))
+                .collect();
+            if !terms.is_empty() {
+                let sum: u32 = terms.iter().sum();
+                let number = board.solution_value();
+                let rest = if sum <= number { format!("{} to go", number - sum) } else { format!("{} too much", sum - number) };
+                let terms: Vec<String> = terms.iter().map(|term| term.to_string()).collect();
+                style.draw_text(window, &format!("{} = {}, {}", terms.join(" + "), sum, rest), 18, theme.text,
+                                Vector2f::new(submit_button_bounds(window.size()).left, 408.0));
+            }
+        }
+
+        {
+            // the last few wrong attempts, written as place value and the rejected digit
+            let attempts = board.wrong_attempts();
+            let shown = &attempts[attempts.len().saturating_sub(WRONG_HISTORY_LENGTH)..];
+            if !shown.is_empty() {
+                let mut lines = vec!["Wrong tries:".to_string()];
+                for attempt in shown.iter().rev() {
+                    lines.push(format!("{}: {:X}", board.place_value(attempt.slot), attempt.value));
+                }
+                style.draw_text(window, &lines.join("\n"), 16, theme.wrong, Vector2f::new(10.0, 10.0));
+            }
+        }
+
+        if let Some(hint) = board.hint() {
+            let place_value = board.place_value(hint.slot);
+            let line = match hint.largest_power {
+                None => "Nothing left, only zeros from here".to_string(),
+                Some(power) if board.base() == 2 && hint.level >= 2 => format!("{} - {} = {}", hint.remaining, power, hint.remaining - power),
+                Some(power) if board.base() == 2 => format!("{} still fits into {}", power, hint.remaining),
+                // with bigger digits it's about how often the place value fits
+                Some(_) if hint.level >= 2 => format!("{} ÷ {} = {} R {}", hint.remaining, place_value, hint.remaining / place_value, hint.remaining % place_value),
+                Some(_) => format!("How often does {} fit into {}?", place_value, hint.remaining),
+            };
+            style.draw_text(window, &line, 20, theme.highlight, Vector2f::new(submit_button_bounds(window.size()).left + 140.0, 442.0));
+        }
+
+        if board.is_lsb_first() {
+            // the repeated division by 2 (or the base): every step produces the next digit from the right
+            // finished steps show their remainder, the current one is left for the player
+            let number = board.solution_value();
+            let base = board.base();
+            let finished_steps = board.filled_from_right();
+            for step in 0..(finished_steps + 1).min(board.len()) {
+                let dividend = number / base.pow(step as u32);
+                let line = if step < finished_steps {
+                    format!("{} ÷ {} = {} R {:X}", dividend, base, dividend / base, dividend % base)
+                } else {
+                    format!("{} ÷ {} = {} R ?", dividend, base, dividend / base)
+                };
+                style.draw_text(window, &line, 16, theme.text, Vector2f::new(600.0, 262.0 + 18.0 * step as f32));
+            }
+        }
+
+        // the button, which checks the whole answer at once
+        style.draw_button(window, submit_button_bounds(window.size()), "Check", false);
+
+        window.draw(&text_origin);
+
+        // draw the snowman, we all love
+        let (snowman, snowman_scale) = self.snowman_shape(&resources, current_frame, snowman_state);
         {
             // create VertexArray from drawing
             // +1 because we have to add another white vertex to hide the origin
             let mut drawing = VertexArray::new(sfml::graphics::PrimitiveType::LINE_STRIP, snowman.len() + 1);
             drawing.append(&Vertex::new(
                 Vector2f::new(self.snowman_pos.x + snowman[0].x * snowman_scale.x, self.snowman_pos.y - snowman[0].y * snowman_scale.y),
-                Color::WHITE, Vector2f::new(0.0, 0.0)));
+                theme.background, Vector2f::new(0.0, 0.0)));
             for point in &snowman {
                 drawing.append(&Vertex::new(
                     Vector2f::new(self.snowman_pos.x + point.x * snowman_scale.x, self.snowman_pos.y - point.y * snowman_scale.y),
-                    Color::BLACK, Vector2f::new(0.0, 0.0)))
+                    theme.text, Vector2f::new(0.0, 0.0)))
             }
             window.draw(&drawing);
         }
@@ -153,39 +325,40 @@ impl Renderer {
             // Hat Magic (simulates gravity)
             // to not have the hat flying, it checks the height of the snowman on two sides
             // using this information, the hat shouldn't have any problems, when the snowman is morphing
-
-            // left part of hat
-            let max_left_hat_pos_y = get_max_height_at(self.hat_left.x, &snowman);
-            if max_left_hat_pos_y >= self.hat_left.y - 0.055
-            { self.hat_left = Vector2f::new(self.hat_left.x, max_left_hat_pos_y) } else { self.hat_left = Vector2f::new(self.hat_left.x, self.hat_left.y - 0.055); }
-
-            // right part of hat
-            let max_right_hat_pos_y = get_max_height_at(self.hat_right.x, &snowman);
-            if max_right_hat_pos_y >= self.hat_right.y - 0.055
-            { self.hat_right = Vector2f::new(self.hat_right.x, max_right_hat_pos_y) } else { self.hat_right = Vector2f::new(self.hat_right.x, self.hat_right.y - 0.055); }
+            if self.hat_frame != Some(current_frame) {
+                self.hat_frame = Some(current_frame);
+
+                // left part of hat
+                let max_left_hat_pos_y = get_max_height_at(self.hat_left.x, &snowman);
+                if max_left_hat_pos_y >= self.hat_left.y - 0.055
+                { self.hat_left = Vector2f::new(self.hat_left.x, max_left_hat_pos_y) } else { self.hat_left = Vector2f::new(self.hat_left.x, self.hat_left.y - 0.055); }
+
+                // right part of hat
+                let max_right_hat_pos_y = get_max_height_at(self.hat_right.x, &snowman);
+                if max_right_hat_pos_y >= self.hat_right.y - 0.055
+                { self.hat_right = Vector2f::new(self.hat_right.x, max_right_hat_pos_y) } else { self.hat_right = Vector2f::new(self.hat_right.x, self.hat_right.y - 0.055); }
+            }
 
             // draw hat to window
-            let mut hat = VertexArray::new(sfml::graphics::PrimitiveType::LINE_STRIP, HAT.len());
+            let mut hat = VertexArray::new(sfml::graphics::PrimitiveType::LINE_STRIP, hat_shape.len());
             let modifier = (self.hat_right.y - self.hat_left.y) / (self.hat_right.x - self.hat_left.x);
 
             hat.append(&Vertex::new(
                 Vector2f::new(
-                    self.snowman_pos.x + (hat_holding_modifier.x + self.hat_left.x + HAT[0].x) * snowman_scale.x,
-                    self.snowman_pos.y - ((self.hat_left.y + hat_holding_modifier.y + HAT[0].y + (modifier * (HAT[0].x))) * snowman_scale.y),
+                    self.snowman_pos.x + (hat_holding_modifier.x + self.hat_left.x + hat_shape[0].x) * snowman_scale.x,
+                    self.snowman_pos.y - ((self.hat_left.y + hat_holding_modifier.y + hat_shape[0].y + (modifier * (hat_shape[0].x))) * snowman_scale.y),
                 ),
-                Color::WHITE, Vector2f::new(0.0, 0.0)));
-            for point in HAT {
+                theme.background, Vector2f::new(0.0, 0.0)));
+            for point in hat_shape {
                 hat.append(&Vertex::new(
                     Vector2f::new(
                         self.snowman_pos.x + (hat_holding_modifier.x + self.hat_left.x + point.x) * snowman_scale.x,
                         self.snowman_pos.y - ((self.hat_left.y + hat_holding_modifier.y + point.y + (modifier * (point.x))) * snowman_scale.y),
                     ),
-                    Color::BLACK, Vector2f::new(0.0, 0.0)));
+                    theme.text, Vector2f::new(0.0, 0.0)));
             }
             window.draw(&hat);
         }
-
-        window.display();
     }
 }
 
@@ -238,40 +411,32 @@ fn get_max_height_at(x_pos: f32, snowman: &Vec<Vector2f>) -> f32 {
     return max_height;
 }
 
-fn morph_into_christmas_tree(animation_frame: i32, animation_duration: i32) -> Vec<Vector2f> {
+fn morph_into_christmas_tree(snowman: [Vector2f; 17], christmas_tree_shape: [Vector2f; 17], animation_frame: i32, animation_duration: i32) -> Vec<Vector2f> {
     let mut christmas_tree = Vec::with_capacity(17);
-    for point in add_vec_array(SNOWMAN, mul_vec_array(
-        div_vec_array_of_number(sub_vec_array(CHRISTMAS_TREE, SNOWMAN), animation_duration as f32),
+    for point in add_vec_array(snowman, mul_vec_array(
+        div_vec_array_of_number(sub_vec_array(christmas_tree_shape, snowman), animation_duration as f32),
         animation_frame as f32)) {
         christmas_tree.push(point);
     }
     return christmas_tree
 }
 
-fn morph_from_christmas_tree(animation_frame: i32, animation_duration: i32) -> Vec<Vector2f> {
+fn morph_from_christmas_tree(snowman_shape: [Vector2f; 17], christmas_tree: [Vector2f; 17], animation_frame: i32, animation_duration: i32) -> Vec<Vector2f> {
     let mut snowman = Vec::with_capacity(17);
-    for point in add_vec_array(CHRISTMAS_TREE, mul_vec_array(
-        div_vec_array_of_number(sub_vec_array(SNOWMAN, CHRISTMAS_TREE), animation_duration as f32),
+    for point in add_vec_array(christmas_tree, mul_vec_array(
+        div_vec_array_of_number(sub_vec_array(snowman_shape, christmas_tree), animation_duration as f32),
         animation_frame as f32)) {
         snowman.push(point);
     }
     return snowman
 }
 
-fn get_christmas_tree() -> Vec<Vector2f> {
-    let mut christmas_tree = Vec::with_capacity(17);
-    for point in CHRISTMAS_TREE {
-        christmas_tree.push(point);
-    }
-    return christmas_tree
-}
-
-fn get_snowman() -> Vec<Vector2f> {
-    let mut snowman = Vec::with_capacity(17);
-    for point in SNOWMAN {
-        snowman.push(point)
+// returns the shape loaded from disk, if there is one with the right amount of points
+fn shape_or<const N: usize>(resources: &Resources, name: &str, built_in: [Vector2f; N]) -> [Vector2f; N] {
+    match resources.shape(name) {
+        Some(points) => points.as_slice().try_into().unwrap_or(built_in),
+        None => built_in,
     }
-    return snowman
 }
 
 fn add_vec_array(vecarray1: [Vector2f; 17], vecarray2: [Vector2f; 17]) -> [Vector2f; 17] {
@@ -326,17 +491,29 @@ fn mul_vec_array(vecarray: [Vector2f; 17], multiplier: f32) -> [Vector2f; 17] {
 
 impl Renderer {}
 
-pub fn new(snowman_pos: Vector2f, animation_duration: i32) -> Renderer {
-    // load font
-    let font = Font::from_file("font.ttf").unwrap();
+// the area of a bit slot, which reacts to clicks
+pub fn slot_bounds(window_size: Vector2u, slot: usize) -> FloatRect {
+    FloatRect::new(window_size.x as f32 / 3.25 + (70 * slot) as f32 - 15.0, 490.0, 45.0, 45.0)
+}
+
+pub fn slot_at(window_size: Vector2u, slot_count: usize, x: i32, y: i32) -> Option<usize> {
+    (0..slot_count).find(|slot| slot_bounds(window_size, *slot).contains2(x as f32, y as f32))
+}
+
+pub fn submit_button_bounds(window_size: Vector2u) -> FloatRect {
+    FloatRect::new(window_size.x as f32 / 3.25 - 15.0, 435.0, 120.0, 40.0)
+}
 
+pub fn new(snowman_pos: Vector2f, animation_duration: i32, options: GameOptions, resources: Rc<RefCell<Resources>>) -> Renderer {
     Renderer {
         snowman_pos,
         snowman_scale: Vector2f::new(25.0, 25.0),
         snowman_idle_amplifier: 1.5,
         animation_duration,
+        options,
         hat_left: Vector2f::new(3.5, 20.0),
         hat_right: Vector2f::new(6.5, 20.0),
-        font,
+        hat_frame: None,
+        resources,
     }
 }
@@ -0,0 +1,133 @@
+use crate::game::board::MAX_HINT_LEVEL;
+use crate::game::exercise::Conversion;
+
+// a hint, that got shown to the player, every level of it is a record of its own
+// (when it happened is in the inputs)
+#[derive(Copy, Clone)]
+pub struct HintRecord {
+    pub slot: usize,
+    pub level: u8,
+}
+
+#[derive(Copy, Clone)]
+pub enum InputKind {
+    // a digit typed (or a bit pressed on a gamepad)
+    Digit,
+    // a slot clicked, which flips its bit
+    Toggle,
+    // a slot emptied
+    Clear,
+    // the whole answer checked
+    Check,
+    // a hint asked for, with the level it got to
+    Hint(u8),
+}
+
+impl InputKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputKind::Digit => "digit",
+            InputKind::Toggle => "toggle",
+            InputKind::Clear => "clear",
+            InputKind::Check => "check",
+            InputKind::Hint(_) => "hint",
+        }
+    }
+}
+
+// one thing the player did to the slots
+#[derive(Clone)]
+pub struct InputRecord {
+    // since the start of the round, the pause doesn't count
+    pub seconds: f32,
+    pub frame: i32,
+    pub kind: InputKind,
+    pub slot: Option<usize>,
+    // the digit, that belongs into the slot
+    pub expected: Option<u8>,
+    // the digit the player put there
+    pub actual: Option<u8>,
+    // for a check: whether the whole answer was right
+    pub is_correct: Option<bool>,
+}
+
+// What happened during one round, kept so teachers can see how a number went
+#[derive(Clone)]
+pub struct RoundStats {
+    pub number: u32,
+    pub conversion: Conversion,
+    pub hints: Vec<HintRecord>,
+    pub wrong_attempts: usize,
+    pub is_solved: bool,
+    // how long the number took, the pause doesn't count
+    pub seconds: f32,
+    // for every slot (left to right): was it right without a wrong try and without being revealed
+    pub first_try: Vec<bool>,
+    // the difficulty level it was played at, 0 without adaptive difficulty
+    pub level: usize,
+    // the time limit ran out before it was solved
+    pub is_timed_out: bool,
+    // every input, in the order it happened
+    pub inputs: Vec<InputRecord>,
+}
+
+const MAX_SCORE: i32 = 100;
+const WRONG_ATTEMPT_PENALTY: i32 = 10;
+// A slot costs by the deepest level of hint it got to, so revealing a bit is the most expensive,
+// but it doesn't cost more than that, no matter how many steps it took to get there.
+const HINT_PENALTIES: [i32; MAX_HINT_LEVEL as usize + 1] = [0, 10, 20, 30];
+
+impl RoundStats {
+    pub fn score(&self) -> i32 {
+        if !self.is_solved {
+            return 0;
+        }
+        (MAX_SCORE - self.wrong_attempts as i32 * WRONG_ATTEMPT_PENALTY - self.hint_penalty()).max(0)
+    }
+
+    fn hint_penalty(&self) -> i32 {
+        let mut deepest: Vec<(usize, u8)> = Vec::new();
+        for hint in &self.hints {
+            match deepest.iter_mut().find(|(slot, _)| *slot == hint.slot) {
+                Some((_, level)) => *level = (*level).max(hint.level),
+                None => deepest.push((hint.slot, hint.level)),
+            }
+        }
+        deepest.iter().map(|(_, level)| HINT_PENALTIES[*level as usize]).sum()
+    }
+
+    // solved without a single wrong try or hint
+    pub fn is_flawless(&self) -> bool {
+        self.is_solved && self.wrong_attempts == 0 && self.hints.is_empty()
+    }
+
+    // how many bits were given away by the last hint level
+    pub fn revealed_bits(&self) -> usize {
+        self.hints.iter().filter(|hint| hint.level == MAX_HINT_LEVEL).count()
+    }
+
+    pub fn summary(&self) -> String {
+        let outcome = if self.is_solved { "solved" } else if self.is_timed_out { "out of time" } else { "not solved" };
+        let mut summary = format!("Number {} ({}): {}, {} wrong, {} hints ({} bits revealed), score {}",
+                                  self.number, self.conversion.name(), outcome, self.wrong_attempts, self.hints.len(), self.revealed_bits(), self.score());
+        if self.level > 0 {
+            summary.push_str(&format!(", level {}", self.level));
+        }
+        summary
+    }
+}
+
+pub fn new(number: u32) -> RoundStats {
+    RoundStats {
+        number,
+        conversion: Conversion::DecToBin,
+        hints: Vec::new(),
+        wrong_attempts: 0,
+        is_solved: false,
+        seconds: 0.0,
+        first_try: Vec::new(),
+        level: 0,
+        is_timed_out: false,
+        inputs: Vec::new(),
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::config::Config;
+use crate::game::round_stats::RoundStats;
+
+/*
+Picks the numbers of a session with spaced repetition, separately for every profile.
+Every number, that got played, is a card with an interval (in rounds) and an ease:
+a number solved quickly and without mistakes comes back after interval * ease rounds,
+a slow one a little later than last time and a missed one right in the next session.
+The rounds of all sessions are counted, so the intervals carry over from one session to the next.
+
+On top of that the two halves of a number (the high and the low nibble) keep an error rate,
+so numbers, that haven't been played yet, but share a pattern with troublesome ones, come first.
+ */
+
+const FIRST_INTERVAL: f32 = 4.0;
+const START_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+// more than this per bit is a slow answer
+const SLOW_SECONDS_PER_BIT: f32 = 3.0;
+// how much a single round changes the error rate of a nibble
+const PATTERN_WEIGHT: f32 = 0.3;
+
+#[derive(Copy, Clone)]
+struct Card {
+    interval: f32,
+    ease: f32,
+    // the round the number is due again
+    due: u32,
+}
+
+pub struct Scheduler {
+    // how many rounds the profile has played so far
+    clock: u32,
+    cards: HashMap<u8, Card>,
+    // error rates from 0 to 1, of the high and the low nibble
+    high_patterns: [f32; 16],
+    low_patterns: [f32; 16],
+}
+
+enum Grade {
+    Missed,
+    Slow,
+    Good,
+}
+
+fn grade(stats: &RoundStats) -> Grade {
+    if !stats.is_solved || stats.wrong_attempts > 0 || !stats.hints.is_empty() {
+        Grade::Missed
+    } else if stats.seconds > SLOW_SECONDS_PER_BIT * stats.first_try.len().max(1) as f32 {
+        Grade::Slow
+    } else {
+        Grade::Good
+    }
+}
+
+fn error_rate(patterns: &mut [f32; 16], pattern: usize, was_wrong: bool) {
+    let target = if was_wrong { 1.0 } else { 0.0 };
+    patterns[pattern] += (target - patterns[pattern]) * PATTERN_WEIGHT;
+}
+
+impl Scheduler {
+    pub fn record(&mut self, stats: &RoundStats) {
+        let Ok(number) = u8::try_from(stats.number) else { return };
+        self.clock += 1;
+        let card = self.cards.get(&number).copied();
+        let (interval, ease) = match (grade(stats), card) {
+            (Grade::Missed, Some(card)) => (1.0, (card.ease - 0.2).max(MIN_EASE)),
+            (Grade::Missed, None) => (1.0, START_EASE - 0.2),
+            (Grade::Slow, Some(card)) => (card.interval * 1.2, (card.ease - 0.15).max(MIN_EASE)),
+            (Grade::Slow, None) => (FIRST_INTERVAL / 2.0, START_EASE - 0.15),
+            (Grade::Good, Some(card)) => (card.interval * card.ease, card.ease),
+            (Grade::Good, None) => (FIRST_INTERVAL, START_EASE),
+        };
+        self.cards.insert(number, Card { interval, ease, due: self.clock + interval.round().max(1.0) as u32 });
+
+        // the digits of a decimal or hex answer are no bits, so they tell nothing about the nibbles
+        if !stats.conversion.is_binary() {
+            return;
+        }
+        // the slots go from left to right, so the last one is the bit of the 1
+        let width = stats.first_try.len();
+        let is_wrong = |nibble: std::ops::Range<usize>| {
+            let bits: Vec<bool> = nibble.filter(|position| *position < width).map(|position| stats.first_try[width - 1 - position]).collect();
+            if bits.is_empty() { None } else { Some(bits.contains(&false)) }
+        };
+        // a number with 4 bits or less doesn't play its high nibble at all
+        if let Some(is_wrong) = is_wrong(4..8) {
+            error_rate(&mut self.high_patterns, (number >> 4) as usize, is_wrong);
+        }
+        if let Some(is_wrong) = is_wrong(0..4) {
+            error_rate(&mut self.low_patterns, (number & 0x0f) as usize, is_wrong);
+        }
+    }
+
+    fn pattern_weakness(&self, number: u8) -> f32 {
+        self.high_patterns[(number >> 4) as usize] + self.low_patterns[(number & 0x0f) as usize]
+    }
+
+    // The numbers, that are due, come first (the longest overdue before the others),
+    // the rest are new numbers, the weaker their patterns the more likely.
+    // Only the candidates can be picked, every number once per session, as long as there are enough.
+    // The same seeded rng and the same repetition data pick the same numbers.
+    pub fn pick(&self, count: usize, candidates: &[u8], rng: &mut impl Rng) -> Vec<u8> {
+        let mut due: Vec<(u8, u32)> = self.cards.iter()
+            .filter(|(number, card)| candidates.contains(number) && card.due < self.clock + count as u32)
+            .map(|(number, card)| (*number, card.due))
+            .collect();
+        due.sort_by_key(|(number, due)| (*due, *number));
+        let mut numbers: Vec<u8> = due.into_iter().take(count).map(|(number, _)| number).collect();
+
+        let mut unseen: Vec<u8> = candidates.iter().copied().filter(|number| !self.cards.contains_key(number)).collect();
+        while numbers.len() < count {
+            let number = match unseen.choose_weighted(rng, |number| 1.0 + 4.0 * self.pattern_weakness(*number)) {
+                Ok(number) => *number,
+                // every number got played already, so the ones due next are taken
+                Err(_) => match self.next_due(&numbers, candidates) {
+                    Some(number) => number,
+                    None => *candidates.choose(rng).unwrap_or(&0),
+                },
+            };
+            unseen.retain(|other| *other != number);
+            numbers.push(number);
+        }
+        // the due numbers shouldn't always be at the start
+        numbers.shuffle(rng);
+        numbers
+    }
+
+    fn next_due(&self, taken: &[u8], candidates: &[u8]) -> Option<u8> {
+        self.cards.iter()
+            .filter(|(number, _)| candidates.contains(number) && !taken.contains(number))
+            .min_by_key(|(number, card)| (card.due, **number))
+            .map(|(number, _)| *number)
+    }
+
+    // only into the profile, writing it is up to the caller
+    pub fn save_to(&self, profile: &mut Config) {
+        profile.remove_prefix("srs.");
+        profile.set("srs.clock", &self.clock.to_string());
+        for (number, card) in &self.cards {
+            profile.set(&format!("srs.number.{:03}", number), &format!("{:.2}\t{:.2}\t{}", card.interval, card.ease, card.due));
+        }
+        for pattern in 0..16 {
+            profile.set(&format!("srs.high.{:02}", pattern), &format!("{:.3}", self.high_patterns[pattern]));
+            profile.set(&format!("srs.low.{:02}", pattern), &format!("{:.3}", self.low_patterns[pattern]));
+        }
+    }
+}
+
+fn parse_card(value: &str) -> Option<Card> {
+    let mut fields = value.split('\t');
+    Some(Card {
+        interval: fields.next()?.parse().ok()?,
+        ease: fields.next()?.parse().ok()?,
+        due: fields.next()?.parse().ok()?,
+    })
+}
+
+fn parse_patterns(profile: &Config, prefix: &str) -> [f32; 16] {
+    let mut patterns = [0.0; 16];
+    for (key, value) in profile.with_prefix(prefix) {
+        let pattern: Option<usize> = key[prefix.len()..].parse().ok();
+        if let (Some(pattern), Ok(rate)) = (pattern.filter(|pattern| *pattern < 16), value.parse()) {
+            patterns[pattern] = rate;
+        }
+    }
+    patterns
+}
+
+// the repetition data of the profile in use ("srs." keys), a new profile starts without any cards
+pub fn load(profile: &Config) -> Scheduler {
+    let mut cards = HashMap::new();
+    for (key, value) in profile.with_prefix("srs.number.") {
+        let number = key["srs.number.".len()..].parse::<u8>().ok();
+        match (number, parse_card(value)) {
+            (Some(number), Some(card)) => { cards.insert(number, card); }
+            _ => println!("Ignoring malformed repetition data {} = {}", key, value),
+        }
+    }
+    Scheduler {
+        clock: profile.get("srs.clock").and_then(|clock| clock.parse().ok()).unwrap_or(0),
+        cards,
+        high_patterns: parse_patterns(profile, "srs.high."),
+        low_patterns: parse_patterns(profile, "srs.low."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::config;
+    use crate::game::exercise::Conversion;
+    use crate::game::round_stats;
+
+    // a solved dec→bin round, the slots go from left to right like on the board
+    fn round(number: u32, first_try: &[bool], seconds: f32) -> RoundStats {
+        let mut stats = round_stats::new(number);
+        stats.is_solved = true;
+        stats.seconds = seconds;
+        stats.first_try = first_try.to_vec();
+        stats
+    }
+
+    fn missed(number: u32, bits: usize) -> RoundStats {
+        let mut stats = round(number, &vec![true; bits], 1.0);
+        stats.is_solved = false;
+        stats
+    }
+
+    fn card(scheduler: &Scheduler, number: u8) -> (f32, f32, u32) {
+        let card = scheduler.cards[&number];
+        (card.interval, card.ease, card.due)
+    }
+
+    fn new_scheduler() -> Scheduler {
+        load(&config::load(""))
+    }
+
+    #[test]
+    fn good_rounds_grow_the_interval_by_the_ease() {
+        let mut scheduler = new_scheduler();
+        scheduler.record(&round(5, &[true; 3], 1.0));
+        assert_eq!(card(&scheduler, 5), (4.0, 2.5, 5));
+        scheduler.record(&round(5, &[true; 3], 1.0));
+        assert_eq!(card(&scheduler, 5), (10.0, 2.5, 12));
+    }
+
+    #[test]
+    fn slow_and_missed_rounds_lower_the_ease() {
+        let mut scheduler = new_scheduler();
+        // more than 3 seconds per bit is slow
+        scheduler.record(&round(5, &[true; 3], 10.0));
+        assert_eq!(card(&scheduler, 5), (2.0, 2.35, 3));
+        scheduler.record(&round(5, &[true; 3], 10.0));
+        let (interval, ease, due) = card(&scheduler, 5);
+        assert!((interval - 2.4).abs() < 0.001 && (ease - 2.2).abs() < 0.001 && due == 4);
+
+        // a miss starts over with the next round, but the ease never drops below the minimum
+        for _ in 0..10 {
+            scheduler.record(&missed(5, 3));
+        }
+        assert_eq!(card(&scheduler, 5), (1.0, MIN_EASE, 13));
+        let mut stats = round(5, &[true; 3], 1.0);
+        stats.wrong_attempts = 1;
+        scheduler.record(&stats);
+        assert_eq!(card(&scheduler, 5).0, 1.0);
+    }
+
+    #[test]
+    fn wrong_bits_raise_the_error_rate_of_their_nibble() {
+        let mut scheduler = new_scheduler();
+        // 0x35 with the bit of the 1 wrong: only the low nibble 5 was troublesome
+        let mut first_try = [true; 8];
+        first_try[7] = false;
+        scheduler.record(&round(0x35, &first_try, 1.0));
+        assert!((scheduler.low_patterns[5] - PATTERN_WEIGHT).abs() < 0.001);
+        assert_eq!(scheduler.high_patterns[3], 0.0);
+
+        // a right one brings it back down, a number with 4 bits or less doesn't touch the high nibble
+        scheduler.record(&round(0x05, &[true; 3], 1.0));
+        assert!((scheduler.low_patterns[5] - PATTERN_WEIGHT * (1.0 - PATTERN_WEIGHT)).abs() < 0.001);
+        assert_eq!(scheduler.high_patterns[0], 0.0);
+        assert!(scheduler.pattern_weakness(0x15) > scheduler.pattern_weakness(0x16));
+
+        // the digits of a decimal answer tell nothing about the nibbles
+        let mut stats = round(0x36, &[false; 3], 1.0);
+        stats.conversion = Conversion::BinToDec;
+        scheduler.record(&stats);
+        assert_eq!(scheduler.low_patterns[6], 0.0);
+        assert_eq!(scheduler.high_patterns[3], 0.0);
+    }
+
+    #[test]
+    fn the_same_seed_picks_the_same_numbers() {
+        let mut scheduler = new_scheduler();
+        scheduler.record(&missed(9, 4));
+        scheduler.record(&round(3, &[true; 2], 1.0));
+        let candidates: Vec<u8> = (0..16).collect();
+        let picked = scheduler.pick(4, &candidates, &mut StdRng::seed_from_u64(7));
+        assert_eq!(picked, scheduler.pick(4, &candidates, &mut StdRng::seed_from_u64(7)));
+
+        // the missed number is due, the good one isn't within these four rounds, and no number comes twice
+        assert!(picked.contains(&9));
+        assert!(!picked.contains(&3));
+        let mut unique = picked.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+        assert!(picked.iter().all(|number| candidates.contains(number)));
+    }
+
+    #[test]
+    fn with_every_number_played_the_next_due_are_taken() {
+        let mut scheduler = new_scheduler();
+        for number in 0..4 {
+            scheduler.record(&round(number, &[true; 2], 1.0));
+        }
+        let picked = scheduler.pick(3, &[0, 1, 2, 3], &mut StdRng::seed_from_u64(1));
+        let mut sorted = picked.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn the_profile_keeps_the_repetition_data() {
+        let mut scheduler = new_scheduler();
+        scheduler.record(&round(0x35, &[true; 8], 1.0));
+        scheduler.record(&missed(0x35, 8));
+        let mut profile = config::load("");
+        scheduler.save_to(&mut profile);
+        let loaded = load(&profile);
+        assert_eq!(loaded.clock, 2);
+        assert_eq!(card(&loaded, 0x35), card(&scheduler, 0x35));
+        assert_eq!(loaded.high_patterns[3], scheduler.high_patterns[3]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::game::exercise::Exercise;
+use crate::game::round_stats::RoundStats;
+
+// the mode of a session from an exercise set, it doesn't count for the high scores
+pub const EXERCISE_SET_MODE: &str = "exercise_set";
+// a speedrun races against its personal best instead of the high scores
+pub const SPEEDRUN_MODE: &str = "speedrun";
+
+// A fixed set of exercises, played one after the other, and what happened in every round.
+// Keeping the exercises allows playing the very same set again.
+#[derive(Clone)]
+pub struct Session {
+    exercises: Vec<Exercise>,
+    rounds: Vec<RoundStats>,
+    // see GameOptions::mode_key
+    mode: String,
+}
+
+impl Session {
+    pub fn exercises(&self) -> &[Exercise] {
+        &self.exercises
+    }
+
+    pub fn rounds(&self) -> &[RoundStats] {
+        &self.rounds
+    }
+
+    pub fn next_exercise(&self) -> Option<&Exercise> {
+        self.exercises.get(self.rounds.len())
+    }
+
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    // how many bits the numbers had, with adaptive difficulty the widest one counts
+    pub fn bits(&self) -> usize {
+        self.rounds.iter().map(|round| round.first_try.len()).max().unwrap_or(0)
+    }
+
+    pub fn finish_round(&mut self, stats: RoundStats) {
+        self.rounds.push(stats);
+    }
+
+    // swaps the exercises, that haven't been played yet (after the difficulty changed)
+    pub fn replace_upcoming(&mut self, exercises: Vec<Exercise>) {
+        self.exercises.truncate(self.rounds.len());
+        self.exercises.extend(exercises);
+    }
+
+    // the difficulty levels of the first and the last round, if the difficulty was adaptive
+    pub fn levels(&self) -> Option<(usize, usize)> {
+        let first = self.rounds.first()?.level;
+        let last = self.rounds.last()?.level;
+        if first > 0 { Some((first, last)) } else { None }
+    }
+
+    // only sessions, that got played to the end, make it into the high scores
+    pub fn is_complete(&self) -> bool {
+        self.rounds.len() >= self.exercises.len()
+    }
+
+    pub fn seconds(&self) -> f32 {
+        self.rounds.iter().map(|round| round.seconds).sum()
+    }
+
+    // how many of all bits were right at the first try
+    pub fn accuracy(&self) -> f32 {
+        let tries: Vec<bool> = self.rounds.iter().flat_map(|round| round.first_try.iter().copied()).collect();
+        if tries.is_empty() {
+            return 0.0;
+        }
+        tries.iter().filter(|is_right| **is_right).count() as f32 / tries.len() as f32
+    }
+
+    pub fn total_score(&self) -> i32 {
+        self.rounds.iter().map(|round| round.score()).sum()
+    }
+
+    pub fn solved_count(&self) -> usize {
+        self.rounds.iter().filter(|round| round.is_solved).count()
+    }
+
+    // the most flawless rounds in a row
+    pub fn best_streak(&self) -> usize {
+        let mut best = 0;
+        let mut current = 0;
+        for round in &self.rounds {
+            current = if round.is_flawless() { current + 1 } else { 0 };
+            best = best.max(current);
+        }
+        best
+    }
+
+    // for every place value (index 0 is the 1), how many of the binary rounds got that bit right at the first try
+    pub fn accuracy_per_place_value(&self) -> Vec<Option<f32>> {
+        let binary: Vec<&RoundStats> = self.rounds.iter().filter(|round| round.conversion.is_binary()).collect();
+        let width = binary.iter().map(|round| round.first_try.len()).max().unwrap_or(0);
+        (0..width)
+            .map(|position| {
+                // the slots go from left to right, so the last one is the 1
+                let tries: Vec<bool> = binary.iter()
+                    .filter_map(|round| round.first_try.len().checked_sub(position + 1).map(|slot| round.first_try[slot]))
+                    .collect();
+                fraction_right(&tries)
+            })
+            .collect()
+    }
+
+    // a decimal or hex digit has no place value of a bit, so those rounds only count all together
+    pub fn accuracy_of_other_digits(&self) -> Option<f32> {
+        let tries: Vec<bool> = self.rounds.iter()
+            .filter(|round| !round.conversion.is_binary())
+            .flat_map(|round| round.first_try.iter().copied())
+            .collect();
+        fraction_right(&tries)
+    }
+}
+
+fn fraction_right(tries: &[bool]) -> Option<f32> {
+    if tries.is_empty() {
+        None
+    } else {
+        Some(tries.iter().filter(|is_right| **is_right).count() as f32 / tries.len() as f32)
+    }
+}
+
+pub fn new(exercises: Vec<Exercise>, mode: String) -> Session {
+    Session {
+        exercises,
+        rounds: Vec::new(),
+        mode,
+    }
+}
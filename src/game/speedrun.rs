@@ -0,0 +1,190 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use sfml::graphics::RenderWindow;
+use sfml::system::Vector2u;
+
+use crate::config::Config;
+use crate::game::options::GameOptions;
+use crate::game::recording;
+use crate::game::recording::{Recorder, Recording, Replayer};
+use crate::game::round_stats::RoundStats;
+use crate::resources::Resources;
+use crate::user_data;
+
+/*
+A speedrun plays a fixed set of numbers, the seed decides which, so the same seed is the same race every time.
+The personal best of every seed (and mode) is the recording of that run, in the "speedruns" folder of the user data dir:
+    speedruns/<profile>-<seed>-<mode>.rec      like --record writes it, see recording.rs
+The times of its numbers (the splits) are the ends of its rounds. While racing, the recording is replayed
+next to the player as the ghost, up to the same moment, so it shows where the best run was.
+Only a run with every number solved can become the personal best. The profile only remembers the seed played last:
+    speedrun.seed = 7
+ */
+
+pub const ROUNDS: usize = 10;
+pub const DEFAULT_SEED: u64 = 1;
+
+// the numbers of a seed, zero is left out because there is nothing to race on it
+pub fn numbers(seed: u64) -> Vec<u8> {
+    let candidates: Vec<u8> = (1..=255).collect();
+    candidates.choose_multiple(&mut StdRng::seed_from_u64(seed), ROUNDS).copied().collect()
+}
+
+pub fn last_seed(profile: &Config) -> u64 {
+    profile.get("speedrun.seed").and_then(|seed| seed.parse().ok()).unwrap_or(DEFAULT_SEED)
+}
+
+// what the status line says about the ghost
+#[derive(Copy, Clone)]
+pub struct Ghost {
+    // negative, when the player is ahead
+    pub numbers_ahead: i32,
+    // how the last split compares to the best run, negative is faster
+    pub last_delta: Option<f32>,
+}
+
+pub struct Speedrun {
+    seed: u64,
+    // the recording of the personal best
+    path: PathBuf,
+    // the seconds of every number of the personal best
+    best: Vec<f32>,
+    // the recording of the personal best, until it becomes the ghost
+    best_run: Option<Recording>,
+    // the personal best, replayed up to the moment the player is at
+    ghost: Option<Replayer>,
+    splits: Vec<f32>,
+    is_new_best: bool,
+}
+
+impl Speedrun {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn is_new_best(&self) -> bool {
+        self.is_new_best
+    }
+
+    pub fn has_best(&self) -> bool {
+        self.best.len() == ROUNDS
+    }
+
+    // starts the ghost, a speedrun without a personal best has none
+    pub fn race(&mut self, window_size: Vector2u, resources: Rc<RefCell<Resources>>) {
+        self.ghost = self.best_run.take().map(|best| {
+            let mut ghost = recording::replayer(best, window_size, resources);
+            ghost.mute();
+            ghost
+        });
+    }
+
+    // called every frame with the seconds of the number the player is at, the ghost catches up with them
+    pub fn track(&mut self, seconds: f32) {
+        let now = self.total() + seconds;
+        if let Some(ghost) = &mut self.ghost {
+            while ghost.seconds() < now {
+                if !ghost.play_frame() && !ghost.next_round() {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn finish_round(&mut self, stats: &RoundStats) {
+        self.splits.push(stats.seconds);
+    }
+
+    pub fn total(&self) -> f32 {
+        self.splits.iter().sum()
+    }
+
+    pub fn best_total(&self) -> Option<f32> {
+        if self.has_best() { Some(self.best.iter().sum()) } else { None }
+    }
+
+    // the time so far against the best run after the same number, negative is faster
+    pub fn delta_after(&self, round: usize) -> Option<f32> {
+        if !self.has_best() || round >= self.splits.len() {
+            return None;
+        }
+        let current: f32 = self.splits[..=round].iter().sum();
+        let best: f32 = self.best[..=round].iter().sum();
+        Some(current - best)
+    }
+
+    fn numbers_ahead(&self) -> Option<i32> {
+        self.ghost.as_ref().map(|ghost| ghost.round() as i32 - self.splits.len() as i32)
+    }
+
+    pub fn ghost(&self) -> Option<Ghost> {
+        Some(Ghost {
+            numbers_ahead: self.numbers_ahead()?,
+            last_delta: self.splits.len().checked_sub(1).and_then(|round| self.delta_after(round)),
+        })
+    }
+
+    pub fn render_ghost(&mut self, window: &mut RenderWindow) {
+        let Some(numbers_ahead) = self.numbers_ahead() else { return };
+        if let Some(ghost) = &mut self.ghost {
+            ghost.game().render_as_ghost(window, numbers_ahead);
+        }
+    }
+
+    // a run, that solved every number faster than the best one, replaces it, its recording becomes the new ghost
+    // the old best stays in memory, so the results can still compare with it
+    // only the seed goes into the profile, writing it is up to the caller
+    pub fn save_to(&mut self, profile: &mut Config, rounds: &[RoundStats], recorder: &Recorder) {
+        profile.set("speedrun.seed", &self.seed.to_string());
+        let is_complete = rounds.len() == ROUNDS && rounds.iter().all(|round| round.is_solved);
+        let is_best = is_complete && match self.best_total() {
+            Some(best) => self.total() < best,
+            None => true,
+        };
+        if is_best {
+            recorder.save(&self.path);
+        }
+        self.is_new_best = is_best;
+    }
+}
+
+// the personal best, a broken one is as good as none
+fn best_run_of(path: &Path, options: GameOptions) -> Option<Recording> {
+    fs::metadata(path).ok()?;
+    let path = path.to_string_lossy();
+    match recording::load(&path, options) {
+        Ok(best) if best.rounds.len() == ROUNDS && best.rounds.iter().all(|round| round.end.is_some()) => Some(best),
+        Ok(_) => {
+            println!("Ignoring the personal best {}, it isn't a whole run", path);
+            None
+        }
+        Err(errors) => {
+            println!("Ignoring the broken personal best {}: {}", path, errors.join(", "));
+            None
+        }
+    }
+}
+
+// the mode keeps runs with different rules apart, like the high scores do, the profile keeps the runs of different players apart
+pub fn new(seed: u64, mode_key: &str, profile_file: &str, options: GameOptions) -> Speedrun {
+    let path = user_data::path("speedruns").join(format!("{}-{}-{}.rec", profile_file, seed, mode_key));
+    let best_run = best_run_of(&path, options);
+    let best = best_run.as_ref()
+        .map(|best| best.rounds.iter().filter_map(|round| round.end.as_ref().map(|(seconds, _)| *seconds)).collect())
+        .unwrap_or_default();
+    Speedrun {
+        seed,
+        path,
+        best,
+        best_run,
+        ghost: None,
+        splits: Vec::new(),
+        is_new_best: false,
+    }
+}
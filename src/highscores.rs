@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+
+const PREFIX: &str = "highscore.";
+// kept for every mode and bit width
+pub const MAX_ENTRIES: usize = 10;
+
+pub struct HighScore {
+    pub mode: String,
+    pub bits: usize,
+    pub score: i32,
+    pub seconds: f32,
+    // how many bits were right at the first try, from 0 to 1
+    pub accuracy: f32,
+    // written as YYYY-MM-DD
+    pub date: String,
+    pub name: String,
+}
+
+impl HighScore {
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{:.1}\t{:.3}\t{}\t{}", self.mode, self.bits, self.score, self.seconds, self.accuracy, self.date, self.name)
+    }
+
+    fn parse(line: &str) -> Option<HighScore> {
+        let mut fields = line.splitn(7, '\t');
+        Some(HighScore {
+            mode: fields.next()?.to_string(),
+            bits: fields.next()?.parse().ok()?,
+            score: fields.next()?.parse().ok()?,
+            seconds: fields.next()?.parse().ok()?,
+            accuracy: fields.next()?.parse().ok()?,
+            date: fields.next()?.to_string(),
+            name: fields.next()?.to_string(),
+        })
+    }
+}
+
+// Every profile has its own high scores, each one is a "highscore.<number>" key
+// with tab separated fields: mode, bits, score, seconds, accuracy, date and name
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    // the best first
+    pub fn of(&self, mode: &str, bits: usize) -> Vec<&HighScore> {
+        let mut entries: Vec<&HighScore> = self.entries.iter().filter(|entry| entry.mode == mode && entry.bits == bits).collect();
+        entries.sort_by(|a, b| ranking(a, b));
+        entries
+    }
+
+    // every mode and bit width, that has a score, in a stable order
+    pub fn tables(&self) -> Vec<(String, usize)> {
+        let mut tables: Vec<(String, usize)> = self.entries.iter().map(|entry| (entry.mode.clone(), entry.bits)).collect();
+        tables.sort();
+        tables.dedup();
+        tables
+    }
+
+    pub fn qualifies(&self, mode: &str, bits: usize, score: i32, seconds: f32) -> bool {
+        let entries = self.of(mode, bits);
+        entries.len() < MAX_ENTRIES
+            || entries.last().is_some_and(|worst| score > worst.score || (score == worst.score && seconds < worst.seconds))
+    }
+
+    // adds the score and drops everything, that fell out of its table
+    pub fn add(&mut self, high_score: HighScore) {
+        let (mut table, others): (Vec<HighScore>, Vec<HighScore>) = self.entries.drain(..)
+            .partition(|entry| entry.mode == high_score.mode && entry.bits == high_score.bits);
+        table.push(high_score);
+        table.sort_by(ranking);
+        table.truncate(MAX_ENTRIES);
+        self.entries = others;
+        self.entries.extend(table);
+    }
+
+    pub fn save(&self, profile: &mut Config) {
+        profile.remove_prefix(PREFIX);
+        for (index, entry) in self.entries.iter().enumerate() {
+            profile.set(&format!("{}{:03}", PREFIX, index), &entry.to_line());
+        }
+        if let Err(error) = profile.save() {
+            println!("Could not save the high scores: {}", error);
+        }
+    }
+}
+
+// the higher score wins, for equal scores the faster one
+fn ranking(a: &HighScore, b: &HighScore) -> Ordering {
+    b.score.cmp(&a.score).then(a.seconds.total_cmp(&b.seconds))
+}
+
+// the high scores of the profile in use, broken entries are skipped
+pub fn load(profile: &Config) -> HighScores {
+    let mut entries = Vec::new();
+    for (key, line) in profile.with_prefix(PREFIX) {
+        match HighScore::parse(line) {
+            Some(entry) => entries.push(entry),
+            None => println!("Ignoring malformed high score {}: {}", key, line),
+        }
+    }
+    HighScores { entries }
+}
+
+// today as YYYY-MM-DD, calculated from the days since 1970 (Howard Hinnant's civil_from_days)
+pub fn today() -> String {
+    date_of(now())
+}
+
+// seconds since 1970
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+pub fn date_of(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
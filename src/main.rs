@@ -1,9 +1,21 @@
-use std::process::exit;
+use std::cell::RefCell;
+use std::rc::Rc;
 
-use sfml::graphics::RenderWindow;
+use sfml::graphics::{RenderTarget, RenderWindow};
 use sfml::window::Style;
 
+mod arguments;
+mod bindings;
+mod config;
+mod export;
 mod game;
+mod highscores;
+mod profile;
+mod resources;
+mod scene;
+mod theme;
+mod ui;
+mod user_data;
 
 fn main() {
     let mut window = RenderWindow::new(
@@ -15,13 +27,50 @@ fn main() {
     window.set_mouse_cursor_visible(true);
     window.set_vertical_sync_enabled(true); // VSync
 
-    loop {
-        // rand ist so schlau, dass es den Ziel-Typen erkennt
-        let mut game = game::new(rand::random(), 120);
-        game.game_loop(&mut window);
-        if game.got_closed_by_user()
-        {
-            exit(0);
+    // loaded once, every scene gets to share them
+    let (profile, profile_config) = profile::open();
+    let mut context = scene::Context {
+        resources: Rc::new(RefCell::new(resources::load_default(&profile_config))),
+        bindings: Rc::new(RefCell::new(bindings::load())),
+        options: game::options::load(&profile_config),
+        numbers: game::number_source::load(&profile_config),
+        profile,
+        profile_config,
+        window_size: window.size(),
+        frame_seconds: 0.0,
+    };
+
+    let mut stack: Vec<Box<dyn scene::Scene>> = vec![Box::new(scene::main_menu::new())];
+    // --exercises <file> starts right into an exercise set, --shuffle mixes it (in the same order again with --seed)
+    if let Some(path) = arguments::value_of("--exercises") {
+        match game::exercise::load(&path) {
+            Ok(exercises) => stack.push(Box::new(scene::game_scene::with_exercise_set(exercises, arguments::has("--shuffle"), &context))),
+            Err(errors) => {
+                println!("Could not play the exercise set {}:", path);
+                for error in errors {
+                    println!("  {}", error);
+                }
+            }
+        }
+    }
+    // --replay <file> plays a recorded session again (see --record)
+    if let Some(path) = arguments::value_of("--replay") {
+        match game::recording::load(&path, context.options) {
+            Ok(recording) => stack.push(Box::new(scene::replay::new(recording, &context))),
+            Err(errors) => {
+                println!("Could not replay {}:", path);
+                for error in errors {
+                    println!("  {}", error);
+                }
+            }
         }
     }
+    // new players get the tutorial once, --tutorial shows it again
+    // it would end in a session of its own, so not when the game starts right into an exercise set or a replay
+    let wants_tutorial = context.profile_config.get("tutorial.done") != Some("true") || arguments::has("--tutorial");
+    if wants_tutorial && stack.len() == 1 {
+        stack.push(Box::new(scene::tutorial::new(&scene::tutorial::script_from_config(), &context)));
+    }
+
+    scene::run(&mut window, &mut context, stack);
 }
@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::bindings::CONFIG_PATH;
+use crate::config;
+use crate::config::Config;
+use crate::game::session::Session;
+use crate::user_data;
+
+/*
+Every player has a profile: one file in the "profiles" folder of the user data dir,
+written in the same format as the config file. It holds everything that belongs to the player:
+the game options ("game."), the colours ("theme"), whether the tutorial was seen,
+the statistics ("stats."), the unlocked content ("unlock.") and the high scores ("highscore.").
+A profile can be backed up or moved to another PC by copying its file.
+The key bindings stay in the config file, they belong to the keyboard, not to the player.
+ */
+
+const DIR_NAME: &str = "profiles";
+const EXTENSION: &str = "cfg";
+const DEFAULT_NAME: &str = "Player";
+pub const MAX_NAME_LENGTH: usize = 16;
+
+// What a profile unlocks by playing, with what it takes. Only extra content gets unlocked,
+// the ways of entering a number (free entry, right to left) are there to be taught from the start.
+pub struct Unlock {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub requirement: &'static str,
+}
+
+pub const SPEEDRUN: Unlock = Unlock { key: "speedrun", name: "Speedrun", requirement: "finish a session with 80% right" };
+
+#[derive(Clone)]
+pub struct Profile {
+    // the name of the file without its extension, it never changes
+    pub file: String,
+    pub name: String,
+}
+
+fn dir() -> PathBuf {
+    user_data::path(DIR_NAME)
+}
+
+fn path_of(file: &str) -> String {
+    dir().join(format!("{}.{}", file, EXTENSION)).to_string_lossy().into_owned()
+}
+
+// all profiles, sorted by name
+pub fn list() -> Vec<Profile> {
+    let mut profiles = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir()) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().and_then(|extension| extension.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let Some(file) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            let name = config::load(&path_of(file)).get("profile.name").unwrap_or(file).to_string();
+            profiles.push(Profile { file: file.to_string(), name });
+        }
+    }
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    profiles
+}
+
+// names end up in line based files, so tabs and line breaks have to go
+pub fn clean_name(name: &str) -> String {
+    let name: String = name.chars().filter(|character| !character.is_control()).take(MAX_NAME_LENGTH).collect();
+    match name.trim() {
+        "" => DEFAULT_NAME.to_string(),
+        name => name.to_string(),
+    }
+}
+
+// the file name is made from the name, a number is added, if it's taken already
+fn free_file_name(name: &str) -> String {
+    let base: String = name.chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let base = if base.trim_matches('_').is_empty() { "player".to_string() } else { base };
+    let mut file = base.clone();
+    let mut number = 2;
+    while fs::metadata(path_of(&file)).is_ok() {
+        file = format!("{}_{}", base, number);
+        number += 1;
+    }
+    file
+}
+
+pub fn create(name: &str) -> Profile {
+    let name = clean_name(name);
+    let file = free_file_name(&name);
+    let mut profile = config::load(&path_of(&file));
+    profile.set("profile.name", &name);
+    if let Err(error) = profile.save() {
+        println!("Could not create the profile {}: {}", name, error);
+    }
+    Profile { file, name }
+}
+
+// The profile in use, the config file remembers its file. It's read once at the start,
+// this is the only place, where a profile gets created without being asked for: the first one.
+pub fn open() -> (Profile, Config) {
+    let config = config::load(CONFIG_PATH);
+    let remembered = config.get("profile").filter(|file| fs::metadata(path_of(file)).is_ok());
+    let file = match remembered {
+        Some(file) => file.to_string(),
+        None => {
+            let profile = list().into_iter().next().unwrap_or_else(|| create(DEFAULT_NAME));
+            select(&profile.file);
+            profile.file
+        }
+    };
+    let profile = load(&file);
+    let name = profile.get("profile.name").unwrap_or(DEFAULT_NAME).to_string();
+    (Profile { file, name }, profile)
+}
+
+pub fn select(file: &str) {
+    let mut config = config::load(CONFIG_PATH);
+    config.set("profile", file);
+    if let Err(error) = config.save() {
+        println!("Could not save the config: {}", error);
+    }
+}
+
+pub fn load(file: &str) -> Config {
+    config::load(&path_of(file))
+}
+
+// everything, that changed in the profile in use, gets written at once
+pub fn save(profile: &Config) {
+    if let Err(error) = profile.save() {
+        println!("Could not save the profile: {}", error);
+    }
+}
+
+pub fn is_unlocked(profile: &Config, unlock: &Unlock) -> bool {
+    profile.get(&format!("unlock.{}", unlock.key)) == Some("true")
+}
+
+fn add_to(profile: &mut Config, key: &str, amount: f64) {
+    let old: f64 = profile.get(key).and_then(|value| value.parse().ok()).unwrap_or(0.0);
+    profile.set(key, &(old + amount).to_string());
+}
+
+// a short line about how much and how well someone played
+pub fn stats_line(profile: &Config) -> String {
+    let stat = |key: &str| profile.get(key).and_then(|value| value.parse::<f64>().ok()).unwrap_or(0.0);
+    let rounds = stat("stats.rounds");
+    let solved = if rounds > 0.0 { stat("stats.solved") / rounds * 100.0 } else { 0.0 };
+    format!("{} sessions, {} rounds, {:.0}% solved, {:.0} min played",
+            stat("stats.sessions"), rounds, solved, stat("stats.seconds") / 60.0)
+}
+
+// adds a played session to the statistics of the profile, returns what it unlocked
+pub fn record_session(profile: &mut Config, session: &Session) -> Vec<&'static Unlock> {
+    add_to(profile, "stats.sessions", 1.0);
+    add_to(profile, "stats.rounds", session.rounds().len() as f64);
+    add_to(profile, "stats.solved", session.solved_count() as f64);
+    add_to(profile, "stats.score", session.total_score() as f64);
+    add_to(profile, "stats.seconds", session.seconds() as f64);
+    let best_streak: usize = profile.get("stats.best_streak").and_then(|value| value.parse().ok()).unwrap_or(0);
+    profile.set("stats.best_streak", &best_streak.max(session.best_streak()).to_string());
+
+    let mut unlocked = Vec::new();
+    let earned = [
+        (&SPEEDRUN, session.is_complete() && session.accuracy() >= 0.8),
+    ];
+    for (unlock, is_earned) in earned {
+        if is_earned && !is_unlocked(profile, unlock) {
+            profile.set(&format!("unlock.{}", unlock.key), "true");
+            unlocked.push(unlock);
+        }
+    }
+    unlocked
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::fs;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use sfml::audio::{Sound, SoundBuffer, SoundSource, SoundStatus};
+use sfml::graphics::{Font, Texture};
+use sfml::SfBox;
+use sfml::system::Vector2f;
+
+use crate::config::Config;
+use crate::theme;
+use crate::theme::Theme;
+
+pub const MAX_VOLUME: f32 = 100.0;
+
+// A cached resource remembers where it came from and how old the file was,
+// so it can be reloaded when somebody changes it on disk
+struct Cached<T> {
+    path: String,
+    modified: Option<SystemTime>,
+    value: T,
+}
+
+// A sound, that plays, keeps its buffer alive, even when the cache got a newer one in the meantime.
+// The old buffer is freed with the last sound, that plays it.
+struct Playing {
+    // dropped before the buffer, because the fields are dropped in this order
+    sound: Sound<'static>,
+    // only there to be kept alive
+    _buffer: Rc<SfBox<SoundBuffer>>,
+}
+
+impl Playing {
+    fn new(buffer: &Rc<SfBox<SoundBuffer>>) -> Playing {
+        let buffer = Rc::clone(buffer);
+        // SAFETY: the buffer sits on the heap of SFML, so it doesn't move, and the Rc next to the sound
+        // keeps it there until the sound is gone
+        let sound = Sound::with_buffer(unsafe { &*(&**buffer as *const SoundBuffer) });
+        Playing { sound, _buffer: buffer }
+    }
+}
+
+pub struct Resources {
+    fonts: HashMap<String, Cached<SfBox<Font>>>,
+    shapes: HashMap<String, Cached<Vec<Vector2f>>>,
+    sounds: HashMap<String, Cached<Rc<SfBox<SoundBuffer>>>>,
+    textures: HashMap<String, Cached<SfBox<Texture>>>,
+    theme: Theme,
+    // a sound stops, once it gets dropped, so the ones still playing are kept here
+    playing: Vec<Playing>,
+    // from 0 (silent) to 100
+    volume: f32,
+}
+
+impl Resources {
+    pub fn load_font(&mut self, name: &str, path: &str) -> bool {
+        load_into(&mut self.fonts, name, path, Font::from_file)
+    }
+
+    pub fn load_shape(&mut self, name: &str, path: &str) -> bool {
+        load_into(&mut self.shapes, name, path, shape_from_file)
+    }
+
+    pub fn load_sound(&mut self, name: &str, path: &str) -> bool {
+        load_into(&mut self.sounds, name, path, sound_from_file)
+    }
+
+    pub fn load_texture(&mut self, name: &str, path: &str) -> bool {
+        load_into(&mut self.textures, name, path, Texture::from_file)
+    }
+
+    pub fn font(&self, name: &str) -> Option<&Font> {
+        self.fonts.get(name).map(|cached| &*cached.value)
+    }
+
+    pub fn shape(&self, name: &str) -> Option<&Vec<Vector2f>> {
+        self.shapes.get(name).map(|cached| &cached.value)
+    }
+
+    // a sound, that isn't loaded, stays silent
+    pub fn play(&mut self, name: &str) {
+        self.forget_finished_sounds();
+        if let Some(cached) = self.sounds.get(name) {
+            let mut playing = Playing::new(&cached.value);
+            playing.sound.set_volume(self.volume);
+            playing.sound.play();
+            self.playing.push(playing);
+        }
+    }
+
+    fn forget_finished_sounds(&mut self) {
+        self.playing.retain(|playing| playing.sound.status() == SoundStatus::PLAYING);
+    }
+
+    pub fn texture(&self, name: &str) -> Option<&Texture> {
+        self.textures.get(name).map(|cached| &*cached.value)
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    // every profile has its own colours
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    // and its own volume, sounds, that play already, keep theirs
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, MAX_VOLUME);
+    }
+
+    // checks every cached file for a newer modification time and loads it again
+    // if loading fails (e.g. the file is only half written), the old version is kept
+    pub fn hot_reload(&mut self) {
+        reload_changed(&mut self.fonts, Font::from_file);
+        reload_changed(&mut self.shapes, shape_from_file);
+        reload_changed(&mut self.sounds, sound_from_file);
+        reload_changed(&mut self.textures, Texture::from_file);
+        // so a replaced buffer isn't kept by a sound, that is over already
+        self.forget_finished_sounds();
+    }
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn load_into<T>(cache: &mut HashMap<String, Cached<T>>, name: &str, path: &str, loader: fn(&str) -> Option<T>) -> bool {
+    match loader(path) {
+        Some(value) => {
+            cache.insert(name.to_string(), Cached { path: path.to_string(), modified: modified_time(path), value });
+            true
+        }
+        None => false,
+    }
+}
+
+fn reload_changed<T>(cache: &mut HashMap<String, Cached<T>>, loader: fn(&str) -> Option<T>) {
+    for (name, cached) in cache.iter_mut() {
+        let modified = modified_time(&cached.path);
+        if modified.is_none() || modified == cached.modified {
+            continue;
+        }
+        match loader(&cached.path) {
+            Some(value) => {
+                println!("Reloaded resource {} from {}", name, cached.path);
+                cached.value = value;
+                cached.modified = modified;
+            }
+            None => println!("Could not reload resource {} from {}, keeping the old one", name, cached.path),
+        }
+    }
+}
+
+// shared with the sounds, that play it (see Playing)
+fn sound_from_file(path: &str) -> Option<Rc<SfBox<SoundBuffer>>> {
+    SoundBuffer::from_file(path).map(Rc::new)
+}
+
+// shape files contain one point per line, written as "x y"
+// empty lines and lines starting with # are ignored
+fn shape_from_file(path: &str) -> Option<Vec<Vector2f>> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut points = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut coordinates = line.split_whitespace();
+        let x = coordinates.next()?.parse::<f32>().ok()?;
+        let y = coordinates.next()?.parse::<f32>().ok()?;
+        points.push(Vector2f::new(x, y));
+    }
+    Some(points)
+}
+
+pub fn new() -> Resources {
+    Resources {
+        fonts: HashMap::new(),
+        shapes: HashMap::new(),
+        sounds: HashMap::new(),
+        textures: HashMap::new(),
+        theme: theme::default_theme(),
+        playing: Vec::new(),
+        volume: MAX_VOLUME,
+    }
+}
+
+// everything the game knows about, loaded once at startup
+// only the font is mandatory, all the other files are optional and replace the built-in versions
+pub fn load_default(profile: &Config) -> Resources {
+    let mut resources = new();
+    if !resources.load_font("default", "font.ttf") {
+        panic!("Could not load font.ttf");
+    }
+    resources.load_shape("snowman", "shapes/snowman.shape");
+    resources.load_shape("christmas_tree", "shapes/christmas_tree.shape");
+    resources.load_shape("hat", "shapes/hat.shape");
+    resources.load_sound("right", "sounds/right.ogg");
+    resources.load_sound("wrong", "sounds/wrong.ogg");
+    resources.load_sound("melt", "sounds/melt.ogg");
+    resources.load_texture("background", "textures/background.png");
+    resources.theme = theme::from_config(profile);
+    resources.volume = volume_from_config(profile);
+    resources
+}
+
+// "sound.volume" goes from 0 to 100, without it everything is as loud as it gets
+pub fn volume_from_config(config: &Config) -> f32 {
+    config.get("sound.volume").and_then(|volume| volume.parse::<f32>().ok()).map_or(MAX_VOLUME, |volume| volume.clamp(0.0, MAX_VOLUME))
+}
@@ -0,0 +1,167 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sfml::graphics::{RenderTarget, RenderWindow};
+use sfml::system::{Clock, Vector2u};
+use sfml::window::Event;
+
+use crate::bindings::Bindings;
+use crate::config::Config;
+use crate::game::game_tasks::GameTasks;
+use crate::game::number_source::NumberSource;
+use crate::game::options::GameOptions;
+use crate::game::{input, joystick};
+use crate::profile::Profile;
+use crate::resources::Resources;
+
+mod exercise_set;
+pub mod game_scene;
+mod highscores;
+pub mod main_menu;
+mod mode_select;
+mod pause;
+mod profiles;
+pub mod replay;
+mod results;
+pub mod settings;
+mod speedrun;
+mod statistics;
+pub mod tutorial;
+
+// everything the scenes share, loaded once at startup
+pub struct Context {
+    pub resources: Rc<RefCell<Resources>>,
+    pub bindings: Rc<RefCell<Bindings>>,
+    // the options of the profile in use
+    pub options: GameOptions,
+    // the profile in use and what's in it, read once and written, whenever something in it changed
+    pub profile: Profile,
+    pub profile_config: Config,
+    // what the sessions are made of
+    pub numbers: NumberSource,
+    pub window_size: Vector2u,
+    // how long the last frame took
+    pub frame_seconds: f32,
+}
+
+// what the scene stack should do after a scene handled something
+pub enum Transition {
+    Stay,
+    // opens a scene on top of the current one
+    Push(Box<dyn Scene>),
+    // closes the current scene and goes back to the one below
+    Pop,
+    // swaps the current scene for another one
+    Replace(Box<dyn Scene>),
+    Quit,
+}
+
+/*
+A scene is one screen of the game: the main menu, the game itself, the pause overlay...
+Only the scene on top of the stack gets tasks and updates,
+the scenes below it are frozen until they are on top again.
+ */
+pub trait Scene {
+    // Most scenes only need the tasks of the input layer.
+    // A scene, that needs the raw event (like capturing a new key binding), can take it here,
+    // every event it doesn't return a transition for goes on to the input layer.
+    fn handle_event(&mut self, _event: &Event, _context: &mut Context) -> Option<Transition> {
+        None
+    }
+
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition;
+
+    // called once per frame
+    fn update(&mut self, _context: &mut Context) -> Transition {
+        Transition::Stay
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context);
+
+    // overlays are drawn on top of the scene below them instead of replacing it
+    fn is_overlay(&self) -> bool {
+        false
+    }
+}
+
+// applies the transition, returns false, once the stack is empty
+fn apply(stack: &mut Vec<Box<dyn Scene>>, transition: Transition) -> bool {
+    match transition {
+        Transition::Stay => {}
+        Transition::Push(scene) => stack.push(scene),
+        Transition::Pop => {
+            stack.pop();
+        }
+        Transition::Replace(scene) => {
+            stack.pop();
+            stack.push(scene);
+        }
+        Transition::Quit => stack.clear(),
+    }
+    !stack.is_empty()
+}
+
+// runs the scenes until the last one is closed or the window gets closed
+pub fn run(window: &mut RenderWindow, context: &mut Context, mut stack: Vec<Box<dyn Scene>>) {
+    let mut input = input::new(Rc::clone(&context.bindings));
+    let mut clock = Clock::start();
+    let mut current_frame: i32 = 0;
+
+    while !stack.is_empty() {
+        context.window_size = window.size();
+
+        while let Some(event) = window.poll_event() {
+            let Some(scene) = stack.last_mut() else { break };
+            let transition = match scene.handle_event(&event, context) {
+                Some(transition) => transition,
+                None => match input.parse_input(event).unwrap() {
+                    // every scene gets the chance to save what it has, before the game closes
+                    GameTasks::Close => {
+                        for scene in stack.iter_mut().rev() {
+                            scene.handle_task(GameTasks::Close, context);
+                        }
+                        Transition::Quit
+                    }
+                    task => scene.handle_task(task, context),
+                },
+            };
+            if !apply(&mut stack, transition) {
+                return;
+            }
+        }
+        for task in input.update_joysticks(joystick::read_all()) {
+            let Some(scene) = stack.last_mut() else { break };
+            let transition = scene.handle_task(task, context);
+            if !apply(&mut stack, transition) {
+                return;
+            }
+        }
+
+        let transition = stack.last_mut().unwrap().update(context);
+        if !apply(&mut stack, transition) {
+            return;
+        }
+
+        // looking at the files once a second is more than enough
+        if current_frame % 60 == 0 {
+            context.resources.borrow_mut().hot_reload();
+        }
+
+        // draw from the last scene, that covers the whole window
+        let first_visible = stack.iter().rposition(|scene| !scene.is_overlay()).unwrap_or(0);
+        for scene in stack[first_visible..].iter_mut() {
+            scene.render(window, context);
+        }
+        window.display();
+
+        // some things to get an fps counter
+        let elapsed_time = clock.elapsed_time().as_milliseconds();
+        let mut fps = 0;
+        if elapsed_time != 0 { // to remove a error, in which a number gets divided by zero
+            fps = 1000_i32 / elapsed_time;
+        }
+        window.set_title(&format!("Frametime: {}, FPS: {}", elapsed_time, fps));
+        context.frame_seconds = clock.restart().as_seconds();
+        current_frame += 1;
+    }
+}
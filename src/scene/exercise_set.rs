@@ -0,0 +1,74 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::game::exercise;
+use crate::game::game_tasks::GameTasks;
+use crate::profile;
+use crate::scene::{game_scene, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{column_of, Anchor};
+use crate::ui::widget::{button, label, list_view, text_field, toggle};
+
+const FILE: usize = 1;
+const SHUFFLED: usize = 2;
+const START: usize = 3;
+const ERRORS: usize = 4;
+const BACK: usize = 5;
+
+// plays an exercise set from a file, the file and the order are remembered by the profile
+pub struct ExerciseSet {
+    form: Form,
+}
+
+impl ExerciseSet {
+    fn remember(&self, context: &mut Context) {
+        let config = &mut context.profile_config;
+        config.set("exercises.file", self.form.text(FILE));
+        config.set("exercises.shuffled", if self.form.is_on(SHUFFLED) { "true" } else { "false" });
+        profile::save(config);
+    }
+}
+
+impl Scene for ExerciseSet {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            // a broken file shows what's wrong with it, line by line
+            UiEvent::Activated(FILE | START) => {
+                self.remember(context);
+                match exercise::load(self.form.text(FILE)) {
+                    Ok(exercises) => Transition::Replace(Box::new(game_scene::with_exercise_set(exercises, self.form.is_on(SHUFFLED), context))),
+                    Err(errors) => {
+                        self.form.set_items(ERRORS, errors);
+                        Transition::Stay
+                    }
+                }
+            }
+            UiEvent::Activated(BACK) | UiEvent::Back => {
+                self.remember(context);
+                Transition::Pop
+            }
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+pub fn new(context: &Context) -> ExerciseSet {
+    let config = &context.profile_config;
+    ExerciseSet {
+        form: ui::form::new(vec![
+            label("Exercise set", 40),
+            text_field("File", config.get("exercises.file").unwrap_or("exercises.txt"), 60).with_width(720.0),
+            toggle("Shuffled", config.get("exercises.shuffled") == Some("true")),
+            button("Start"),
+            list_view(Vec::new(), 6).with_width(720.0),
+            button("Back"),
+        ], column_of(6), Anchor::TopLeft),
+    }
+}
@@ -0,0 +1,232 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use sfml::graphics::RenderWindow;
+
+use crate::arguments;
+use crate::bindings::Action;
+use crate::export;
+use crate::game;
+use crate::game::analytics::Analytics;
+use crate::game::difficulty::{Difficulty, Level};
+use crate::game::exercise::Exercise;
+use crate::game::game_tasks::GameTasks;
+use crate::game::recording::Recorder;
+use crate::game::scheduler::Scheduler;
+use crate::game::session::Session;
+use crate::game::speedrun::Speedrun;
+use crate::game::{analytics, difficulty, exercise, recording, scheduler, session, speedrun, Game};
+use crate::profile;
+use crate::scene::{pause, results, settings, Context, Scene, Transition};
+
+// Plays the numbers of a session one after the other, until they are done or the player leaves.
+// The rounds themselves are still played by Game, this scene only opens the pause and the settings
+// and collects the stats for the results screen.
+pub struct GameScene {
+    game: Game,
+    session: Session,
+    // learns from every round, which numbers should come back soon
+    scheduler: Scheduler,
+    // where the profile goes wrong, for the statistics screen
+    analytics: Analytics,
+    // only there with adaptive difficulty
+    difficulty: Option<Difficulty>,
+    // a session of picked numbers gets new ones, when the difficulty changes, a replayed one keeps its numbers
+    picks_numbers: bool,
+    // picks the numbers, when the difficulty changes
+    rng: StdRng,
+    // everything the rounds get, so they can be replayed
+    recorder: Recorder,
+    // only there in a speedrun
+    speedrun: Option<Speedrun>,
+    // set by the pause overlay
+    is_leaving: Rc<Cell<bool>>,
+}
+
+impl GameScene {
+    fn start_round(&mut self, exercise: &Exercise, context: &Context) {
+        let level = level_of(&self.difficulty);
+        self.recorder.start_round(exercise, level);
+        self.game = new_round(exercise, level, context);
+    }
+}
+
+impl Scene for GameScene {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match task {
+            // switching to another window pauses too, so nothing runs on while nobody is looking
+            GameTasks::Triggered(Action::Pause | Action::Back) | GameTasks::FocusLost => {
+                Transition::Push(Box::new(pause::new(Rc::clone(&self.is_leaving), context.resources.borrow().volume())))
+            }
+            GameTasks::Triggered(Action::OpenSettings) => Transition::Push(Box::new(settings::new())),
+            GameTasks::Close => {
+                println!("{}", self.game.stats().summary());
+                self.recorder.save(&recording::path_from_arguments());
+                Transition::Quit
+            }
+            _ => {
+                self.recorder.task(&task, context.window_size);
+                self.game.handle_task(task, context.window_size);
+                Transition::Stay
+            }
+        }
+    }
+
+    fn update(&mut self, context: &mut Context) -> Transition {
+        if self.is_leaving.get() && !self.game.is_stopped() {
+            self.recorder.task(&GameTasks::Triggered(Action::Quit), context.window_size);
+            self.game.quit();
+        }
+        self.recorder.frame(context.frame_seconds);
+        self.game.update(context.frame_seconds);
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.track(self.game.seconds());
+            self.game.set_ghost(speedrun.ghost());
+        }
+        if !self.game.is_stopped() {
+            return Transition::Stay;
+        }
+        let stats = self.game.stats();
+        println!("{}", stats.summary());
+        self.recorder.end_round(&stats);
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.finish_round(&stats);
+        }
+        // everything the round taught goes into the profile, which is written once
+        let profile = &mut context.profile_config;
+        self.scheduler.record(&stats);
+        self.scheduler.save_to(profile);
+        self.analytics.record(&stats);
+        self.analytics.save_to(profile);
+        let new_level = match &mut self.difficulty {
+            Some(difficulty) => {
+                let has_changed = difficulty.record(&stats);
+                difficulty.save_to(profile);
+                if has_changed { Some(difficulty.level()) } else { None }
+            }
+            None => None,
+        };
+        // the round has to be finished first, or replacing the upcoming exercises would drop the one just played
+        self.session.finish_round(stats);
+        if let Some(level) = new_level.filter(|_| self.picks_numbers) {
+            let upcoming = self.session.exercises().len().saturating_sub(self.session.rounds().len());
+            let numbers = self.scheduler.pick(upcoming, &candidates(context, level), &mut self.rng);
+            self.session.replace_upcoming(numbers.into_iter().map(exercise::plain).collect());
+        }
+        match self.session.next_exercise() {
+            Some(exercise) if !self.game.got_closed_by_user() => {
+                profile::save(&context.profile_config);
+                let exercise = exercise.clone();
+                self.start_round(&exercise, context);
+                Transition::Stay
+            }
+            _ => {
+                self.recorder.save(&recording::path_from_arguments());
+                let unlocked = profile::record_session(&mut context.profile_config, &self.session);
+                if let Some(dir) = export::dir_from_arguments() {
+                    match export::write(&self.session, &context.profile.name, &dir) {
+                        Ok(path) => println!("Exported the session to {}.csv and .json", path.display()),
+                        Err(error) => println!("Could not export the session: {}", error),
+                    }
+                }
+                if let Some(speedrun) = &mut self.speedrun {
+                    speedrun.save_to(&mut context.profile_config, self.session.rounds(), &self.recorder);
+                }
+                profile::save(&context.profile_config);
+                Transition::Replace(Box::new(results::new(&self.session, &unlocked, self.speedrun.as_ref(), context)))
+            }
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, _context: &Context) {
+        self.game.render(window);
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.render_ghost(window);
+        }
+    }
+}
+
+fn new_round(exercise: &Exercise, level: Level, context: &Context) -> Game {
+    game::new(exercise, level, 120, context.options, Rc::clone(&context.resources))
+}
+
+fn level_of(difficulty: &Option<Difficulty>) -> Level {
+    difficulty.as_ref().map_or(difficulty::FIXED, |difficulty| difficulty.level())
+}
+
+fn start(session: Session, difficulty: Option<Difficulty>, picks_numbers: bool, seed: u64, rng: StdRng, context: &Context) -> GameScene {
+    let first = session.next_exercise().cloned().unwrap_or_else(|| exercise::plain(0));
+    let mut recorder = recording::recorder(seed, &context.options);
+    recorder.start_round(&first, level_of(&difficulty));
+    GameScene {
+        game: new_round(&first, level_of(&difficulty), context),
+        session,
+        scheduler: scheduler::load(&context.profile_config),
+        analytics: analytics::load(&context.profile_config),
+        difficulty,
+        picks_numbers,
+        rng,
+        recorder,
+        speedrun: None,
+        is_leaving: Rc::new(Cell::new(false)),
+    }
+}
+
+// The numbers of the source, that fit the level. A source, that has none of them
+// (like 100-200 on level 1), is played as it is, the numbers just get more bits.
+fn candidates(context: &Context, level: Level) -> Vec<u8> {
+    let numbers = context.numbers.numbers();
+    let fitting: Vec<u8> = numbers.iter().copied().filter(|number| *number <= level.max).collect();
+    if fitting.is_empty() { numbers } else { fitting }
+}
+
+fn adaptive_difficulty(context: &Context) -> Option<Difficulty> {
+    if context.options.is_adaptive { Some(difficulty::load(&context.profile_config)) } else { None }
+}
+
+// --seed <number> picks the same numbers again (as long as the repetition data of the profile is the same)
+fn seed() -> u64 {
+    arguments::value_of("--seed")
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random)
+}
+
+// a session of the numbers the profile should practise next, from the source and as big as the difficulty allows
+pub fn new(context: &Context) -> GameScene {
+    let difficulty = adaptive_difficulty(context);
+    let seed = seed();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let numbers = scheduler::load(&context.profile_config).pick(context.options.rounds_per_session as usize, &candidates(context, level_of(&difficulty)), &mut rng);
+    let session = session::new(numbers.into_iter().map(exercise::plain).collect(), context.options.mode_key());
+    start(session, difficulty, true, seed, rng, context)
+}
+
+// plays the given exercises (again)
+pub fn with_exercises(exercises: Vec<Exercise>, context: &Context) -> GameScene {
+    let seed = seed();
+    start(session::new(exercises, context.options.mode_key()), adaptive_difficulty(context), false, seed, StdRng::seed_from_u64(seed), context)
+}
+
+// the numbers of the seed, against the personal best of the profile (with the same options)
+pub fn speedrun(seed: u64, context: &Context) -> GameScene {
+    let exercises = speedrun::numbers(seed).into_iter().map(exercise::plain).collect();
+    let mut scene = start(session::new(exercises, session::SPEEDRUN_MODE.to_string()), None, false, seed, StdRng::seed_from_u64(seed), context);
+    let mut speedrun = speedrun::new(seed, &context.options.mode_key(), &context.profile.file, context.options);
+    speedrun.race(context.window_size, Rc::clone(&context.resources));
+    scene.speedrun = Some(speedrun);
+    scene
+}
+
+// an exercise set of a teacher is played just like it was written, the difficulty stays out of it
+// a shuffled one is mixed by the seed, so --seed plays it in the same order again
+pub fn with_exercise_set(mut exercises: Vec<Exercise>, is_shuffled: bool, context: &Context) -> GameScene {
+    let seed = seed();
+    let mut rng = StdRng::seed_from_u64(seed);
+    if is_shuffled {
+        exercises.shuffle(&mut rng);
+    }
+    start(session::new(exercises, session::EXERCISE_SET_MODE.to_string()), None, false, seed, rng, context)
+}
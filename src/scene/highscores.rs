@@ -0,0 +1,55 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::game::game_tasks::GameTasks;
+use crate::game::options;
+use crate::highscores;
+use crate::scene::{Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{column_of, Anchor};
+use crate::ui::widget::{button, label, list_view};
+
+const BACK: usize = 2;
+
+// every table of the high scores, one after the other
+pub struct HighScoreList {
+    form: Form,
+}
+
+impl Scene for HighScoreList {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            UiEvent::Activated(BACK) | UiEvent::Back => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+pub fn new(context: &Context) -> HighScoreList {
+    let high_scores = highscores::load(&context.profile_config);
+    let mut lines = Vec::new();
+    for (mode, bits) in high_scores.tables() {
+        lines.push(format!("{}, {} bits", options::mode_name(&mode), bits));
+        for (place, entry) in high_scores.of(&mode, bits).iter().enumerate() {
+            lines.push(format!("  {}. {}: {} points, {:.1} s, {:.0}% right, {}",
+                               place + 1, entry.name, entry.score, entry.seconds, entry.accuracy * 100.0, entry.date));
+        }
+    }
+    if lines.is_empty() {
+        lines.push("No high scores yet, finish a session to get one".to_string());
+    }
+    HighScoreList {
+        form: ui::form::new(vec![
+            label("High scores", 40),
+            list_view(lines, 15).with_width(720.0),
+            button("Back"),
+        ], column_of(3), Anchor::TopLeft),
+    }
+}
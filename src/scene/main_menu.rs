@@ -0,0 +1,79 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::game::game_tasks::GameTasks;
+use crate::scene::{game_scene, highscores, mode_select, profiles, settings, statistics, tutorial, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{button, label};
+
+const PLAYER: usize = 1;
+const PLAY: usize = 2;
+const PROFILES: usize = 3;
+const GAME_MODE: usize = 4;
+const TUTORIAL: usize = 5;
+const HIGH_SCORES: usize = 6;
+const STATISTICS: usize = 7;
+const KEY_BINDINGS: usize = 8;
+const QUIT: usize = 9;
+
+// the first scene, it stays at the bottom of the stack until the game ends
+pub struct MainMenu {
+    form: Form,
+}
+
+impl Scene for MainMenu {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            UiEvent::Activated(PLAY) => Transition::Push(Box::new(game_scene::new(context))),
+            UiEvent::Activated(PROFILES) => Transition::Push(Box::new(profiles::new(context))),
+            UiEvent::Activated(GAME_MODE) => Transition::Push(Box::new(mode_select::new(context))),
+            UiEvent::Activated(TUTORIAL) => Transition::Push(Box::new(tutorial::new(&tutorial::script_from_config(), context))),
+            UiEvent::Activated(HIGH_SCORES) => Transition::Push(Box::new(highscores::new(context))),
+            UiEvent::Activated(STATISTICS) => Transition::Push(Box::new(statistics::new(context))),
+            UiEvent::Activated(KEY_BINDINGS) => Transition::Push(Box::new(settings::new())),
+            UiEvent::Activated(QUIT) | UiEvent::Back => Transition::Quit,
+            _ => Transition::Stay,
+        }
+    }
+
+    // the profile might have changed in the meantime
+    fn update(&mut self, context: &mut Context) -> Transition {
+        self.form.set_text(PLAYER, &format!("Playing as {}", context.profile.name));
+        Transition::Stay
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+pub fn new() -> MainMenu {
+    MainMenu {
+        form: ui::form::new(vec![
+            label("MrBinaer", 48),
+            label("", 22),
+            button("Play"),
+            button("Profiles"),
+            button("Game mode"),
+            button("Tutorial"),
+            button("High scores").with_width(195.0),
+            button("Statistics").with_width(195.0),
+            button("Key bindings"),
+            button("Quit"),
+        ], Layout::Column(vec![
+            Layout::Widget(0),
+            Layout::Widget(PLAYER),
+            Layout::Widget(PLAY),
+            Layout::Widget(PROFILES),
+            Layout::Widget(GAME_MODE),
+            Layout::Widget(TUTORIAL),
+            Layout::Row(vec![Layout::Widget(HIGH_SCORES), Layout::Widget(STATISTICS)]),
+            Layout::Widget(KEY_BINDINGS),
+            Layout::Widget(QUIT),
+        ]), Anchor::Center),
+    }
+}
@@ -0,0 +1,135 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::config::Config;
+use crate::game::game_tasks::GameTasks;
+use crate::game::options::{BitOrder, EntryMode, GameOptions, MAX_ROUNDS_PER_SESSION};
+use crate::game::{number_source, options};
+use crate::profile;
+use crate::profile::Unlock;
+use crate::scene::{exercise_set, game_scene, speedrun, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{button, label, number_field, text_field, toggle, Widget};
+
+const FREE_ENTRY: usize = 1;
+const RIGHT_TO_LEFT: usize = 2;
+const PLACE_VALUES: usize = 3;
+const BREAKDOWN: usize = 4;
+const ADAPTIVE: usize = 5;
+const ROUNDS: usize = 6;
+const NUMBERS: usize = 7;
+const NUMBERS_MESSAGE: usize = 8;
+const START: usize = 9;
+const EXERCISE_SET: usize = 10;
+const SPEEDRUN: usize = 11;
+
+const COLUMN_WIDTH: f32 = 360.0;
+
+// picks how the next rounds are played, the choice is saved for the next start
+pub struct ModeSelect {
+    form: Form,
+}
+
+impl ModeSelect {
+    fn options(&self) -> GameOptions {
+        GameOptions {
+            entry_mode: if self.form.is_on(FREE_ENTRY) { EntryMode::Free } else { EntryMode::Guided },
+            bit_order: if self.form.is_on(RIGHT_TO_LEFT) { BitOrder::LsbFirst } else { BitOrder::MsbFirst },
+            show_place_values: self.form.is_on(PLACE_VALUES),
+            show_breakdown: self.form.is_on(BREAKDOWN),
+            rounds_per_session: self.form.number(ROUNDS) as u32,
+            is_adaptive: self.form.is_on(ADAPTIVE),
+        }
+    }
+}
+
+impl Scene for ModeSelect {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            // a source, that can't be read, keeps the last good one
+            UiEvent::Changed(NUMBERS) => {
+                match number_source::parse(self.form.text(NUMBERS)) {
+                    Ok(source) => {
+                        self.form.set_text(NUMBERS_MESSAGE, &format!("{} numbers", source.numbers().len()));
+                        context.numbers = source;
+                    }
+                    Err(error) => self.form.set_text(NUMBERS_MESSAGE, &error),
+                }
+                Transition::Stay
+            }
+            UiEvent::Changed(_) => {
+                context.options = self.options();
+                Transition::Stay
+            }
+            UiEvent::Activated(START) => {
+                options::save(&context.options, &mut context.profile_config);
+                number_source::save(&context.numbers, &mut context.profile_config);
+                Transition::Replace(Box::new(game_scene::new(context)))
+            }
+            UiEvent::Activated(EXERCISE_SET) => {
+                options::save(&context.options, &mut context.profile_config);
+                number_source::save(&context.numbers, &mut context.profile_config);
+                Transition::Push(Box::new(exercise_set::new(context)))
+            }
+            UiEvent::Activated(SPEEDRUN) => {
+                options::save(&context.options, &mut context.profile_config);
+                number_source::save(&context.numbers, &mut context.profile_config);
+                Transition::Push(Box::new(speedrun::new(context)))
+            }
+            UiEvent::Back => {
+                options::save(&context.options, &mut context.profile_config);
+                number_source::save(&context.numbers, &mut context.profile_config);
+                Transition::Pop
+            }
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+// content, that isn't unlocked yet, only tells what it takes
+fn unlockable_button(unlock: &Unlock, profile: &Config) -> Widget {
+    if profile::is_unlocked(profile, unlock) {
+        button(unlock.name)
+    } else {
+        label(&format!("{} (locked: {})", unlock.name, unlock.requirement), 20)
+    }
+}
+
+pub fn new(context: &Context) -> ModeSelect {
+    let options = context.options;
+    let profile = &context.profile_config;
+    let widgets = vec![
+        label("Game mode", 48),
+        toggle("Free entry", options.entry_mode == EntryMode::Free),
+        toggle("Right to left", options.bit_order == BitOrder::LsbFirst),
+        toggle("Place values", options.show_place_values),
+        toggle("Sum of the bits", options.show_breakdown),
+        toggle("Adaptive difficulty", options.is_adaptive),
+        number_field("Rounds", options.rounds_per_session as i64, 1, MAX_ROUNDS_PER_SESSION as i64),
+        text_field("Numbers", context.numbers.spec(), 40),
+        label(&format!("{} numbers (like 0-15, pow2, bits=3)", context.numbers.numbers().len()), 16),
+        button("Start"),
+        button("Exercise set"),
+        unlockable_button(&profile::SPEEDRUN, profile),
+    ];
+    let widgets = widgets.into_iter().map(|widget| widget.with_width(COLUMN_WIDTH)).collect();
+    // how a round is played on the left, what gets played on the right
+    let layout = Layout::Column(vec![
+        Layout::Widget(0),
+        Layout::Row(vec![
+            Layout::Column((FREE_ENTRY..=ADAPTIVE).map(Layout::Widget).collect()),
+            Layout::Column((ROUNDS..=SPEEDRUN).map(Layout::Widget).collect()),
+        ]),
+    ]);
+    ModeSelect {
+        form: ui::form::new(widgets, layout, Anchor::Center),
+    }
+}
@@ -0,0 +1,94 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use sfml::graphics::{Color, RectangleShape, RenderTarget, RenderWindow, Shape};
+use sfml::system::Vector2f;
+
+use crate::bindings::Action;
+use crate::game::game_tasks::GameTasks;
+use crate::profile;
+use crate::resources::MAX_VOLUME;
+use crate::scene::{settings, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{column_of, Anchor};
+use crate::ui::widget::{button, label, slider};
+
+const RESUME: usize = 1;
+const VOLUME: usize = 2;
+const KEY_BINDINGS: usize = 3;
+const LEAVE: usize = 4;
+
+const VOLUME_STEP: f32 = 10.0;
+
+// Drawn over the frozen game: as long as the pause is on top, the game doesn't get updated,
+// so its frame, and with it every animation and the time of the round, stands still.
+// Leaving the game from here is told to the game scene through is_leaving,
+// because only the game scene knows how to end the round.
+pub struct Pause {
+    form: Form,
+    is_leaving: Rc<Cell<bool>>,
+    has_volume_changed: bool,
+}
+
+impl Pause {
+    // the volume belongs to the profile, it's saved once the pause is over
+    fn close(&self, context: &mut Context) -> Transition {
+        if self.has_volume_changed {
+            context.profile_config.set("sound.volume", &self.form.value(VOLUME).to_string());
+            profile::save(&context.profile_config);
+        }
+        Transition::Pop
+    }
+}
+
+impl Scene for Pause {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        if let GameTasks::Triggered(Action::Pause) = task {
+            return self.close(context);
+        }
+        match self.form.handle_task(task, context.window_size) {
+            UiEvent::Activated(RESUME) | UiEvent::Back => self.close(context),
+            UiEvent::Changed(VOLUME) => {
+                context.resources.borrow_mut().set_volume(self.form.value(VOLUME));
+                self.has_volume_changed = true;
+                Transition::Stay
+            }
+            UiEvent::Activated(KEY_BINDINGS) => Transition::Push(Box::new(settings::new())),
+            UiEvent::Activated(LEAVE) => {
+                self.is_leaving.set(true);
+                self.close(context)
+            }
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        let background = style.theme.background;
+        let size = context.window_size;
+        let mut veil = RectangleShape::with_size(Vector2f::new(size.x as f32, size.y as f32));
+        veil.set_fill_color(Color::rgba(background.red(), background.green(), background.blue(), 220));
+        window.draw(&veil);
+        self.form.render(window, &style, context.window_size);
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+pub fn new(is_leaving: Rc<Cell<bool>>, volume: f32) -> Pause {
+    Pause {
+        form: ui::form::new(vec![
+            label("Paused", 48),
+            button("Resume"),
+            slider("Volume", volume, 0.0, MAX_VOLUME, VOLUME_STEP),
+            button("Key bindings"),
+            button("Leave the game"),
+        ], column_of(5), Anchor::Center),
+        is_leaving,
+        has_volume_changed: false,
+    }
+}
@@ -0,0 +1,106 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::game::game_tasks::GameTasks;
+use crate::game::{number_source, options};
+use crate::profile;
+use crate::profile::{Profile, MAX_NAME_LENGTH};
+use crate::resources;
+use crate::scene::{tutorial, Context, Scene, Transition};
+use crate::theme;
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{button, label, list_view, text_field};
+
+const LIST: usize = 1;
+const USE_SELECTED: usize = 2;
+const NEW_NAME: usize = 3;
+const CREATE: usize = 4;
+const BACK: usize = 5;
+
+// picks the player, everyone sharing the PC has a profile of their own
+pub struct Profiles {
+    form: Form,
+    profiles: Vec<Profile>,
+}
+
+fn lines(profiles: &[Profile], context: &Context) -> Vec<String> {
+    profiles.iter()
+        .map(|profile| {
+            // two players can have the same name, but never the same file
+            if profile.file == context.profile.file {
+                format!("{} (playing): {}", profile.name, profile::stats_line(&context.profile_config))
+            } else {
+                format!("{}: {}", profile.name, profile::stats_line(&profile::load(&profile.file)))
+            }
+        })
+        .collect()
+}
+
+impl Profiles {
+    // switches everything over to the profile, new players start with the tutorial
+    fn switch_to(&self, profile: &Profile, context: &mut Context) -> Transition {
+        profile::select(&profile.file);
+        let config = profile::load(&profile.file);
+        context.options = options::load(&config);
+        context.numbers = number_source::load(&config);
+        {
+            let mut resources = context.resources.borrow_mut();
+            resources.set_theme(theme::from_config(&config));
+            resources.set_volume(resources::volume_from_config(&config));
+        }
+        context.profile = profile.clone();
+        context.profile_config = config;
+        println!("Playing as {}", profile.name);
+        if context.profile_config.get("tutorial.done") == Some("true") {
+            Transition::Pop
+        } else {
+            Transition::Replace(Box::new(tutorial::new(&tutorial::script_from_config(), context)))
+        }
+    }
+}
+
+impl Scene for Profiles {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            UiEvent::Activated(LIST | USE_SELECTED) => match self.profiles.get(self.form.selected(LIST)) {
+                Some(profile) => self.switch_to(profile, context),
+                None => Transition::Stay,
+            },
+            UiEvent::Activated(NEW_NAME | CREATE) => {
+                let created = profile::create(self.form.text(NEW_NAME));
+                self.switch_to(&created, context)
+            }
+            UiEvent::Activated(BACK) | UiEvent::Back => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+pub fn new(context: &Context) -> Profiles {
+    let profiles = profile::list();
+    Profiles {
+        form: ui::form::new(vec![
+            label("Profiles", 40),
+            list_view(lines(&profiles, context), 8).with_width(720.0),
+            button("Use selected"),
+            text_field("New player", "", MAX_NAME_LENGTH).with_width(480.0),
+            button("Create").with_width(230.0),
+            button("Back"),
+        ], Layout::Column(vec![
+            Layout::Widget(0),
+            Layout::Widget(LIST),
+            Layout::Widget(USE_SELECTED),
+            Layout::Row(vec![Layout::Widget(NEW_NAME), Layout::Widget(CREATE)]),
+            Layout::Widget(BACK),
+        ]), Anchor::TopLeft),
+        profiles,
+    }
+}
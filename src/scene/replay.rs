@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+use sfml::graphics::RenderWindow;
+
+use crate::bindings::Action;
+use crate::game::game_tasks::GameTasks;
+use crate::game::recording;
+use crate::game::recording::{Recording, Replayer};
+use crate::scene::{Context, Scene, Transition};
+
+// Plays a recording again, frame by frame, with the very same tasks and frame lengths.
+// Nothing of it gets saved to the profile, the player can only watch (or leave).
+// At the end of every round the stats are compared with the recorded ones.
+pub struct Replay {
+    replayer: Replayer,
+    differences: Vec<String>,
+}
+
+impl Replay {
+    fn finish_round(&mut self) {
+        let round = self.replayer.round();
+        let stats = self.replayer.game().stats();
+        let summary = format!("{} {}", stats.seconds, stats.summary());
+        println!("Replayed: {}", stats.summary());
+        match self.replayer.recorded_round().and_then(|recorded| recorded.end.as_ref()) {
+            Some((seconds, recorded)) if format!("{} {}", seconds, recorded) != summary => {
+                self.differences.push(format!("round {}: recorded \"{} {}\", replayed \"{}\"", round + 1, seconds, recorded, summary));
+            }
+            Some(_) => {}
+            None => println!("The recording stops in the middle of round {}", round + 1),
+        }
+    }
+
+    fn report(&self) {
+        if self.differences.is_empty() {
+            println!("The replay matches the recording");
+        } else {
+            println!("The replay differs from the recording:");
+            for difference in &self.differences {
+                println!("  {}", difference);
+            }
+        }
+    }
+}
+
+impl Scene for Replay {
+    fn handle_task(&mut self, task: GameTasks, _context: &mut Context) -> Transition {
+        match task {
+            GameTasks::Triggered(Action::Pause | Action::Back | Action::Quit) => Transition::Pop,
+            GameTasks::Close => Transition::Quit,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn update(&mut self, _context: &mut Context) -> Transition {
+        if self.replayer.play_frame() {
+            return Transition::Stay;
+        }
+        self.finish_round();
+        if self.replayer.next_round() {
+            Transition::Stay
+        } else {
+            self.report();
+            Transition::Pop
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, _context: &Context) {
+        self.replayer.game().render(window);
+    }
+}
+
+pub fn new(recording: Recording, context: &Context) -> Replay {
+    println!("Replaying a session with seed {}", recording.seed);
+    Replay {
+        replayer: recording::replayer(recording, context.window_size, Rc::clone(&context.resources)),
+        differences: Vec::new(),
+    }
+}
@@ -0,0 +1,194 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::export;
+use crate::game::game_tasks::GameTasks;
+use crate::game::exercise::Exercise;
+use crate::game::session;
+use crate::game::session::Session;
+use crate::game::speedrun::Speedrun;
+use crate::highscores;
+use crate::highscores::HighScore;
+use crate::profile;
+use crate::profile::{Unlock, MAX_NAME_LENGTH};
+use crate::scene::{game_scene, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{button, label, list_view, text_field, Widget};
+
+// the buttons come after the accuracy of every bit, so they are counted from here
+const SAME_NUMBERS: usize = 0;
+const NEW_NUMBERS: usize = 1;
+const MENU: usize = 2;
+const EXPORT: usize = 3;
+
+// the summary at the end of a session (or after leaving it early)
+pub struct Results {
+    form: Form,
+    first_button: usize,
+    exercises: Vec<Exercise>,
+    is_exercise_set: bool,
+    // a speedrun starts again with the same seed
+    speedrun_seed: Option<u64>,
+    // kept for the export
+    session: Session,
+    // a finished session, that is good enough for the high scores, can be saved under a name
+    high_score: Option<HighScore>,
+    name_field: usize,
+    save_button: usize,
+}
+
+impl Results {
+    fn save_high_score(&mut self, context: &mut Context) {
+        let Some(mut high_score) = self.high_score.take() else { return };
+        high_score.name = profile::clean_name(self.form.text(self.name_field));
+        let mut high_scores = highscores::load(&context.profile_config);
+        high_scores.add(high_score);
+        high_scores.save(&mut context.profile_config);
+        self.form.set_text(self.save_button, "Saved");
+    }
+
+    fn export(&mut self, context: &Context) {
+        let first_button = self.first_button;
+        match export::write(&self.session, &context.profile.name, &export::default_dir()) {
+            Ok(path) => {
+                println!("Exported the session to {}.csv and .json", path.display());
+                self.form.set_text(first_button + EXPORT, "Exported");
+            }
+            Err(error) => {
+                println!("Could not export the session: {}", error);
+                self.form.set_text(first_button + EXPORT, "Export failed");
+            }
+        }
+    }
+}
+
+impl Scene for Results {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            // without a high score, there is neither a name field nor a save button
+            UiEvent::Activated(index) if index < self.first_button && (index == self.name_field || index == self.save_button) => {
+                self.save_high_score(context);
+                Transition::Stay
+            }
+            UiEvent::Activated(index) if index >= self.first_button => match index - self.first_button {
+                SAME_NUMBERS => match self.speedrun_seed {
+                    Some(seed) => Transition::Replace(Box::new(game_scene::speedrun(seed, context))),
+                    None if self.is_exercise_set => Transition::Replace(Box::new(game_scene::with_exercise_set(self.exercises.clone(), false, context))),
+                    None => Transition::Replace(Box::new(game_scene::with_exercises(self.exercises.clone(), context))),
+                },
+                NEW_NUMBERS => Transition::Replace(Box::new(game_scene::new(context))),
+                MENU => Transition::Pop,
+                EXPORT => {
+                    self.export(context);
+                    Transition::Stay
+                }
+                _ => Transition::Stay,
+            },
+            UiEvent::Back => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+// unlocked is, what the session unlocked for the profile, a speedrun is compared with its personal best
+pub fn new(session: &Session, unlocked: &[&Unlock], speedrun: Option<&Speedrun>, context: &Context) -> Results {
+    let rounds = session.rounds();
+    let mut overview = format!("{} of {} solved, {} points, best streak {}",
+                               session.solved_count(), rounds.len(), session.total_score(), session.best_streak());
+    if let Some((first, last)) = session.levels() {
+        overview.push_str(&format!(", level {} to {}", first, last));
+    }
+    if let Some(speedrun) = speedrun {
+        overview.push_str(&format!("\nSpeedrun {}: {:.2} s", speedrun.seed(), speedrun.total()));
+        match speedrun.best_total() {
+            Some(best) if session.is_complete() => overview.push_str(&format!(", {:+.2} s against your best", speedrun.total() - best)),
+            _ => {}
+        }
+        if speedrun.is_new_best() {
+            overview.push_str("\nNew personal best!");
+        }
+    }
+    for unlock in unlocked {
+        overview.push_str(&format!("\nUnlocked: {}", unlock.name));
+    }
+    let lines = rounds.iter().enumerate()
+        .map(|(i, round)| {
+            let outcome = if round.is_solved { "solved" } else if round.is_timed_out { "out of time" } else { "not solved" };
+            let level = if round.level > 0 { format!(", level {}", round.level) } else { String::new() };
+            let split = speedrun.and_then(|speedrun| speedrun.delta_after(i)).map_or(String::new(), |delta| format!(", split {:+.2} s", delta));
+            format!("{} ({}): {}, {:.1} s, {} wrong, {} hints, {} points{}{}",
+                    round.number, round.conversion.name(), outcome, round.seconds, round.wrong_attempts, round.hints.len(), round.score(), level, split)
+        })
+        .collect();
+
+    // everything has to fit into 720x520 inside the margins of an 800x600 window: at most three overview lines
+    // (a speedrun never unlocks anything), six list rows, the accuracy row and the name and button rows
+    let mut widgets: Vec<Widget> = vec![
+        label("Results", 40),
+        label(&overview, 22),
+        list_view(lines, 6).with_width(720.0),
+    ];
+    // how often every bit was right at the first try, under its place value, the highest on the left
+    for (position, fraction) in session.accuracy_per_place_value().into_iter().enumerate().rev() {
+        let text = match fraction {
+            Some(fraction) => format!("{}\n{:.0}%", 1u32 << position, fraction * 100.0),
+            None => format!("{}\n-", 1u32 << position),
+        };
+        widgets.push(label(&text, 18).with_width(60.0));
+    }
+    if let Some(fraction) = session.accuracy_of_other_digits() {
+        widgets.push(label(&format!("dec/hex digits\n{:.0}%", fraction * 100.0), 18).with_width(130.0));
+    }
+    let accuracy_labels = 3..widgets.len();
+
+    let high_scores = highscores::load(&context.profile_config);
+    let high_score = Some(HighScore {
+        mode: session.mode().to_string(),
+        bits: session.bits(),
+        score: session.total_score(),
+        seconds: session.seconds(),
+        accuracy: session.accuracy(),
+        date: highscores::today(),
+        name: String::new(),
+    }).filter(|score| session.is_complete() && score.mode != session::EXERCISE_SET_MODE && score.mode != session::SPEEDRUN_MODE && high_scores.qualifies(&score.mode, score.bits, score.score, score.seconds));
+    let name_field = widgets.len();
+    let save_button = name_field + 1;
+    let mut layout = vec![
+        Layout::Widget(0),
+        Layout::Widget(1),
+        Layout::Widget(2),
+        Layout::Row(accuracy_labels.map(Layout::Widget).collect()),
+    ];
+    if high_score.is_some() {
+        widgets.push(text_field("New high score! Name", &context.profile.name, MAX_NAME_LENGTH).with_width(480.0));
+        widgets.push(button("Save score").with_width(230.0));
+        layout.push(Layout::Row(vec![Layout::Widget(name_field), Layout::Widget(save_button)]));
+    }
+
+    let first_button = widgets.len();
+    widgets.push(button("Same numbers").with_width(170.0));
+    widgets.push(button("New numbers").with_width(170.0));
+    widgets.push(button("Menu").with_width(170.0));
+    widgets.push(button("Export").with_width(170.0));
+    layout.push(Layout::Row((first_button..widgets.len()).map(Layout::Widget).collect()));
+
+    Results {
+        form: ui::form::new(widgets, Layout::Column(layout), Anchor::TopLeft),
+        first_button,
+        exercises: session.exercises().to_vec(),
+        is_exercise_set: session.mode() == session::EXERCISE_SET_MODE,
+        speedrun_seed: speedrun.map(Speedrun::seed),
+        session: session.clone(),
+        high_score,
+        name_field,
+        save_button,
+    }
+}
@@ -0,0 +1,102 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+use sfml::system::Vector2f;
+use sfml::window::{Event, Key};
+
+use crate::bindings::{Action, Bindings, KeyChord, PadInput};
+use crate::game::game_tasks::GameTasks;
+use crate::resources::Resources;
+use crate::scene::{Context, Scene, Transition};
+use crate::ui;
+
+// The settings screen lets the player rebind every action.
+// Navigation uses fixed keys, so a broken binding can never lock anybody out:
+// Up/Down selects an action, Enter waits for a new key chord or joystick button,
+// Backspace clears the action and Escape saves and leaves the screen.
+// That's why this scene reads the raw events instead of the tasks of the input layer.
+pub struct Settings {
+    selected: usize,
+    is_capturing: bool,
+}
+
+impl Scene for Settings {
+    fn handle_event(&mut self, event: &Event, context: &mut Context) -> Option<Transition> {
+        let mut bindings = context.bindings.borrow_mut();
+        match *event {
+            Event::JoystickButtonPressed { button, .. } if self.is_capturing => {
+                bindings.add_pad(Action::ALL[self.selected], PadInput::Button(button));
+                self.is_capturing = false;
+            }
+            Event::JoystickMoved { axis, position, .. } if self.is_capturing && position.abs() > 50.0 => {
+                let pad_input = if position > 0.0 { PadInput::AxisPositive(axis) } else { PadInput::AxisNegative(axis) };
+                bindings.add_pad(Action::ALL[self.selected], pad_input);
+                self.is_capturing = false;
+            }
+            Event::KeyPressed { code, alt, ctrl, shift, system } => {
+                let chord = KeyChord::new(code, alt, ctrl, shift, system);
+                if self.is_capturing {
+                    if !chord.is_modifier_only() {
+                        bindings.add(Action::ALL[self.selected], chord);
+                        self.is_capturing = false;
+                    }
+                    return Some(Transition::Stay);
+                }
+                match code {
+                    Key::UP => self.selected = (self.selected + Action::ALL.len() - 1) % Action::ALL.len(),
+                    Key::DOWN => self.selected = (self.selected + 1) % Action::ALL.len(),
+                    Key::ENTER => self.is_capturing = true,
+                    Key::BACKSPACE | Key::DELETE => bindings.clear(Action::ALL[self.selected]),
+                    Key::ESCAPE => {
+                        bindings.save();
+                        return Some(Transition::Pop);
+                    }
+                    _ => {}
+                }
+            }
+            // everything else (like closing the window) goes through the input layer
+            _ => return None,
+        }
+        Some(Transition::Stay)
+    }
+
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        if let GameTasks::Close = task {
+            context.bindings.borrow().save();
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        render(window, &context.resources.borrow(), &context.bindings.borrow(), self.selected, self.is_capturing);
+    }
+}
+
+fn render(window: &mut RenderWindow, resources: &Resources, bindings: &Bindings, selected: usize, is_capturing: bool) {
+    let style = ui::style(resources);
+    let theme = style.theme;
+    window.clear(theme.background);
+
+    style.draw_text(window, "Key bindings", 40, theme.text, Vector2f::new(40.0, 20.0));
+
+    for (i, action) in Action::ALL.iter().enumerate() {
+        let mut inputs: Vec<String> = bindings.chords_of(*action).iter().map(|chord| chord.to_text()).collect();
+        inputs.extend(bindings.pad_inputs_of(*action).iter().map(|pad_input| format!("Pad {}", pad_input.to_text())));
+        let mut line = format!("{}: {}", action.name(), inputs.join(", "));
+        if i == selected {
+            if is_capturing {
+                line = format!("{}: press a key or joystick button...", action.name());
+            }
+            line = format!("> {}", line);
+        }
+        let colour = if i == selected { theme.highlight } else { theme.text };
+        style.draw_text(window, &line, 22, colour, Vector2f::new(40.0, 80.0 + 30.0 * i as f32));
+    }
+
+    style.draw_text(window, "Enter: add key   Backspace: clear   Escape: save and back", 18, theme.text, Vector2f::new(40.0, 560.0));
+}
+
+pub fn new() -> Settings {
+    Settings {
+        selected: 0,
+        is_capturing: false,
+    }
+}
@@ -0,0 +1,64 @@
+use sfml::graphics::{RenderTarget, RenderWindow};
+
+use crate::game::game_tasks::GameTasks;
+use crate::game::speedrun;
+use crate::scene::{game_scene, Context, Scene, Transition};
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{column_of, Anchor};
+use crate::ui::widget::{button, label, number_field};
+
+const SEED: usize = 1;
+const BEST: usize = 2;
+const START: usize = 3;
+const BACK: usize = 4;
+
+const MAX_SEED: i64 = 999_999;
+
+// picks the seed of a speedrun and shows the personal best to beat
+pub struct SpeedrunSelect {
+    form: Form,
+}
+
+fn best_line(seed: u64, context: &Context) -> String {
+    match speedrun::new(seed, &context.options.mode_key(), &context.profile.file, context.options).best_total() {
+        Some(best) => format!("Personal best: {:.2} s for {} numbers", best, speedrun::ROUNDS),
+        None => format!("No personal best yet, solve all {} numbers", speedrun::ROUNDS),
+    }
+}
+
+impl Scene for SpeedrunSelect {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        let event = self.form.handle_task(task, context.window_size);
+        let seed = self.form.number(SEED) as u64;
+        match event {
+            UiEvent::Changed(SEED) => {
+                self.form.set_text(BEST, &best_line(seed, context));
+                Transition::Stay
+            }
+            UiEvent::Activated(SEED | START) => Transition::Replace(Box::new(game_scene::speedrun(seed, context))),
+            UiEvent::Activated(BACK) | UiEvent::Back => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+    }
+}
+
+pub fn new(context: &Context) -> SpeedrunSelect {
+    let seed = speedrun::last_seed(&context.profile_config).min(MAX_SEED as u64);
+    SpeedrunSelect {
+        form: ui::form::new(vec![
+            label("Speedrun", 40),
+            number_field("Seed", seed as i64, 0, MAX_SEED),
+            label(&best_line(seed, context), 20),
+            button("Start"),
+            button("Back"),
+        ], column_of(5), Anchor::TopLeft),
+    }
+}
@@ -0,0 +1,153 @@
+use sfml::graphics::{Color, FloatRect, RectangleShape, RenderTarget, RenderWindow, Shape, Transformable};
+use sfml::system::Vector2f;
+
+use crate::game::analytics;
+use crate::game::analytics::{Analytics, BIT_POSITIONS};
+use crate::game::game_tasks::GameTasks;
+use crate::scene::{Context, Scene, Transition};
+use crate::theme::Theme;
+use crate::ui;
+use crate::ui::form::{Form, UiEvent};
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{button, label};
+use crate::ui::Style;
+
+const BACK: usize = 1;
+
+const MARGIN: f32 = 40.0;
+// the bar chart of the bit positions
+const BARS_TOP: f32 = 110.0;
+const BAR_HEIGHT: f32 = 110.0;
+const BAR_WIDTH: f32 = 28.0;
+// the heatmap of the numbers, 16 by 16 cells
+const HEATMAP_TOP: f32 = 320.0;
+const CELL: f32 = 13.0;
+const LABEL_WIDTH: f32 = 30.0;
+// that many of the most missed numbers are listed next to the heatmap
+const WORST_COUNT: usize = 8;
+
+// where the profile in use goes wrong: per bit position and per number
+pub struct Statistics {
+    form: Form,
+    analytics: Analytics,
+}
+
+// from the colour of right bits (never wrong) to the one of wrong bits (always wrong)
+fn heat(theme: &Theme, rate: f32) -> Color {
+    let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * rate.clamp(0.0, 1.0)) as u8;
+    Color::rgb(
+        blend(theme.correct.red(), theme.wrong.red()),
+        blend(theme.correct.green(), theme.wrong.green()),
+        blend(theme.correct.blue(), theme.wrong.blue()),
+    )
+}
+
+fn draw_box(window: &mut RenderWindow, bounds: FloatRect, colour: Color) {
+    let mut rectangle = RectangleShape::with_size(Vector2f::new(bounds.width, bounds.height));
+    rectangle.set_position(Vector2f::new(bounds.left, bounds.top));
+    rectangle.set_fill_color(colour);
+    window.draw(&rectangle);
+}
+
+impl Statistics {
+    // Two bars for every bit position, the highest place value on the left like on the board:
+    // how often it was wrong (coloured by how bad it is) and how long it took compared to the slowest one.
+    fn draw_bits(&self, window: &mut RenderWindow, style: &Style, width: f32) {
+        let theme = &style.theme;
+        style.draw_text(window, "Wrong bits (left bar) and seconds per bit (right bar)", 20, theme.text, Vector2f::new(MARGIN, BARS_TOP));
+        let slowest = self.analytics.bits.iter().filter_map(|tally| tally.mean_seconds()).fold(0.0, f32::max);
+        let column_width = width / BIT_POSITIONS as f32;
+        let baseline = BARS_TOP + 30.0 + BAR_HEIGHT;
+        for column in 0..BIT_POSITIONS {
+            let position = BIT_POSITIONS - 1 - column;
+            let tally = &self.analytics.bits[position];
+            let left = MARGIN + column as f32 * column_width + (column_width - 2.0 * BAR_WIDTH - 6.0) / 2.0;
+            let error_bar = FloatRect::new(left, BARS_TOP + 30.0, BAR_WIDTH, BAR_HEIGHT);
+            let latency_bar = FloatRect::new(left + BAR_WIDTH + 6.0, BARS_TOP + 30.0, BAR_WIDTH, BAR_HEIGHT);
+            style.draw_frame(window, error_bar, theme.outline, 1.0);
+            style.draw_frame(window, latency_bar, theme.outline, 1.0);
+            let text = match (tally.error_rate(), tally.mean_seconds()) {
+                (Some(rate), Some(seconds)) => {
+                    let height = BAR_HEIGHT * rate;
+                    draw_box(window, FloatRect::new(error_bar.left, baseline - height, BAR_WIDTH, height), heat(theme, rate));
+                    let height = if slowest > 0.0 { BAR_HEIGHT * seconds / slowest } else { 0.0 };
+                    draw_box(window, FloatRect::new(latency_bar.left, baseline - height, BAR_WIDTH, height), theme.highlight);
+                    format!("{:.0}% {:.1}s", rate * 100.0, seconds)
+                }
+                _ => "-".to_string(),
+            };
+            let column_left = MARGIN + column as f32 * column_width;
+            style.draw_text(window, &(1u32 << position).to_string(), 18, theme.text, Vector2f::new(column_left + 4.0, baseline + 4.0));
+            style.draw_text(window, &text, 14, theme.text, Vector2f::new(column_left + 4.0, baseline + 28.0));
+        }
+    }
+
+    // A cell for every number, a row for every high nibble: the more rounds of it were missed, the hotter.
+    fn draw_numbers(&self, window: &mut RenderWindow, style: &Style) {
+        let theme = &style.theme;
+        style.draw_text(window, "Missed rounds per number", 20, theme.text, Vector2f::new(MARGIN, HEATMAP_TOP));
+        let grid_left = MARGIN + LABEL_WIDTH;
+        let grid_top = HEATMAP_TOP + 30.0;
+        for row in 0..16 {
+            style.draw_text(window, &(row * 16).to_string(), 10, theme.text, Vector2f::new(MARGIN, grid_top + row as f32 * CELL));
+            for column in 0..16 {
+                let tally = &self.analytics.numbers[row * 16 + column];
+                let colour = tally.error_rate().map_or(theme.outline, |rate| heat(theme, rate));
+                draw_box(window, FloatRect::new(grid_left + column as f32 * CELL, grid_top + row as f32 * CELL, CELL - 1.0, CELL - 1.0), colour);
+            }
+        }
+
+        // the legend and the worst numbers go right of the grid
+        let left = grid_left + 16.0 * CELL + 30.0;
+        let mut top = grid_top;
+        for (text, colour) in [("never missed", theme.correct), ("always missed", theme.wrong), ("not played yet", theme.outline)] {
+            draw_box(window, FloatRect::new(left, top + 2.0, CELL, CELL), colour);
+            style.draw_text(window, text, 14, theme.text, Vector2f::new(left + CELL + 8.0, top));
+            top += 20.0;
+        }
+        let mut worst: Vec<(usize, &analytics::Tally)> = self.analytics.numbers.iter().enumerate()
+            .filter(|(_, tally)| tally.wrong > 0)
+            .collect();
+        worst.sort_by(|(_, a), (_, b)| b.error_rate().partial_cmp(&a.error_rate()).unwrap().then(b.wrong.cmp(&a.wrong)));
+        top += 10.0;
+        style.draw_text(window, "Missed most often:", 16, theme.text, Vector2f::new(left, top));
+        for (number, tally) in worst.into_iter().take(WORST_COUNT) {
+            top += 20.0;
+            let line = format!("{} ({:08b}): {} of {}, {:.1} s", number, number, tally.wrong, tally.count, tally.seconds / tally.count as f32);
+            style.draw_text(window, &line, 14, theme.text, Vector2f::new(left, top));
+        }
+    }
+}
+
+impl Scene for Statistics {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match self.form.handle_task(task, context.window_size) {
+            UiEvent::Activated(BACK) | UiEvent::Back => Transition::Pop,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        let resources = context.resources.borrow();
+        let style = ui::style(&resources);
+        window.clear(style.theme.background);
+        self.form.render(window, &style, context.window_size);
+        if self.analytics.has_data() {
+            self.draw_bits(window, &style, context.window_size.x as f32 - 2.0 * MARGIN);
+            self.draw_numbers(window, &style);
+        } else {
+            style.draw_text(window, "No rounds played yet, the statistics fill up while you play", 20, style.theme.text, Vector2f::new(MARGIN, BARS_TOP));
+        }
+    }
+}
+
+// the statistics of the profile in use, as they are right now
+pub fn new(context: &Context) -> Statistics {
+    Statistics {
+        form: ui::form::new(vec![
+            label("Statistics", 40),
+            button("Back").with_width(230.0),
+        ], Layout::Row(vec![Layout::Widget(0), Layout::Widget(BACK)]), Anchor::TopLeft),
+        analytics: analytics::load(&context.profile_config),
+    }
+}
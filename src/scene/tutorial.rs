@@ -0,0 +1,251 @@
+use std::fs;
+use std::rc::Rc;
+
+use sfml::graphics::RenderWindow;
+use sfml::system::Vector2f;
+
+use crate::bindings::{Action, CONFIG_PATH};
+use crate::config;
+use crate::game::board::Board;
+use crate::game::game_tasks::GameTasks;
+use crate::game::renderer::Renderer;
+use crate::game::snowman_states::SnowmanStates;
+use crate::game::{board, renderer};
+use crate::profile;
+use crate::resources::Resources;
+use crate::scene::{game_scene, Context, Scene, Transition};
+use crate::ui;
+
+/*
+A tutorial script is a plain text file with one step per line:
+    say <text>              shows the text and waits for Enter or a click
+    expect <char> <text>    shows the text and waits, until the character got typed
+    convert <number>        walks through converting the number, bit by bit
+Empty lines and lines starting with # are ignored.
+ */
+pub const DEFAULT_SCRIPT: &str = "\
+say Welcome to MrBinaer! Every number can be written with nothing but 0 and 1. Press Enter to go on.
+say Look at the numbers under the slots: these are the place values. Every slot is worth double the one on its right.
+say To convert a number, go from left to right. If the place value fits into what is left, write 1 and subtract it. Otherwise write 0.
+expect 1 Let's try it: type 1.
+convert 5
+convert 12
+convert 200
+say Well done! Now it is your turn. Press Enter to start playing.
+";
+
+enum Step {
+    Say(String),
+    Expect(char, String),
+    Convert(u32),
+}
+
+// turns a script into steps, every line, that can't be understood, is reported with its line number
+fn parse(script: &str) -> (Vec<Step>, Vec<String>) {
+    let mut steps = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        let argument = argument.trim();
+        match command {
+            "say" => steps.push(Step::Say(argument.to_string())),
+            "expect" => {
+                let mut characters = argument.chars();
+                match characters.next() {
+                    Some(expected) => steps.push(Step::Expect(expected, characters.as_str().trim().to_string())),
+                    None => errors.push(format!("line {}: expect needs a character", i + 1)),
+                }
+            }
+            "convert" => match argument.parse::<u8>() {
+                Ok(number) => steps.push(Step::Convert(number as u32)),
+                Err(_) => errors.push(format!("line {}: convert needs a number from 0 to 255, not \"{}\"", i + 1, argument)),
+            },
+            _ => errors.push(format!("line {}: unknown command \"{}\"", i + 1, command)),
+        }
+    }
+    (steps, errors)
+}
+
+// reads the teacher's script, or falls back to the built-in one
+pub fn load_script(path: &str) -> String {
+    match fs::read_to_string(path) {
+        Ok(script) => script,
+        Err(_) => DEFAULT_SCRIPT.to_string(),
+    }
+}
+
+// reads the script the config file points to
+pub fn script_from_config() -> String {
+    let config = config::load(CONFIG_PATH);
+    load_script(config.get("tutorial.script").unwrap_or("tutorial.txt"))
+}
+
+// the tutorial counts as seen (by the profile in use), once it got finished or skipped
+fn mark_as_done(context: &mut Context) {
+    context.profile_config.set("tutorial.done", "true");
+    profile::save(&context.profile_config);
+}
+
+// Plays the script step by step and hands off to the normal game at the end.
+// Back (Escape) skips the rest of the tutorial.
+pub struct Tutorial {
+    steps: Vec<Step>,
+    step_index: usize,
+    is_new_step: bool,
+    renderer: Renderer,
+    board: Board,
+    origin: String,
+    current_frame: i32,
+    // what the player did since the last update
+    is_continue: bool,
+    typed: Option<char>,
+    prompt: String,
+    // the answer to the last bit of a conversion
+    reaction: String,
+}
+
+impl Scene for Tutorial {
+    fn handle_task(&mut self, task: GameTasks, context: &mut Context) -> Transition {
+        match task {
+            GameTasks::Triggered(Action::Back | Action::Quit) => {
+                println!("Tutorial skipped");
+                mark_as_done(context);
+                return Transition::Pop;
+            }
+            GameTasks::Triggered(Action::Submit) | GameTasks::ClickPressed(..) => self.is_continue = true,
+            GameTasks::TextEntered(character) => self.typed = Some(character),
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn update(&mut self, context: &mut Context) -> Transition {
+        if self.step_index >= self.steps.len() {
+            mark_as_done(context);
+            return Transition::Replace(Box::new(game_scene::new(context)));
+        }
+        if self.is_new_step {
+            self.is_new_step = false;
+            self.reaction.clear();
+            if let Step::Convert(number) = self.steps[self.step_index] {
+                self.board = board::new(board::bits_of(number, 8), 2, false, false);
+                self.origin = number.to_string();
+            }
+        }
+        let is_continue = self.is_continue;
+        let typed = self.typed;
+        self.is_continue = false;
+        self.typed = None;
+        let board = &mut self.board;
+
+        // a conversion gets a new prompt for every bit
+        let mut is_step_done = false;
+        self.prompt = match &self.steps[self.step_index] {
+            Step::Say(text) => {
+                is_step_done = is_continue;
+                text.clone()
+            }
+            Step::Expect(expected, text) => {
+                is_step_done = typed == Some(*expected);
+                text.clone()
+            }
+            Step::Convert(number) => match board.next_empty() {
+                Some(slot) => {
+                    let remaining = board.remaining_before(slot);
+                    let place_value = board.place_value(slot);
+                    if let Some(bit) = typed.and_then(|character| character.to_digit(2)) {
+                        let bit = bit as u8;
+                        if bit == board.expected(slot) {
+                            board.confirm(slot, bit);
+                            self.reaction = if bit == 1 {
+                                format!("Right, {} - {} = {} is left.", remaining, place_value, remaining - place_value)
+                            } else {
+                                format!("Right, {} is too big, {} is still left.", place_value, remaining)
+                            };
+                        } else {
+                            board.record_wrong_attempt(slot, bit, self.current_frame);
+                            self.reaction = if bit == 1 {
+                                format!("Not quite: {} is bigger than {}.", place_value, remaining)
+                            } else {
+                                format!("Not quite: {} fits into {}.", place_value, remaining)
+                            };
+                        }
+                    }
+                    format!("Does {} fit into {}? Type 1 for yes and 0 for no.", place_value, remaining)
+                }
+                None => {
+                    is_step_done = is_continue;
+                    let bits: String = (0..board.len()).map(|slot| board.expected(slot).to_string()).collect();
+                    format!("Done! {} is {} in binary. Press Enter to go on.", number, bits)
+                }
+            },
+        };
+        if is_step_done {
+            self.step_index += 1;
+            self.is_new_step = true;
+        }
+        self.current_frame += 1;
+        Transition::Stay
+    }
+
+    fn render(&mut self, window: &mut RenderWindow, context: &Context) {
+        self.renderer.render(window, &self.board, &self.origin, self.current_frame, SnowmanStates::Idle);
+        draw_prompt(window, &context.resources.borrow(), &self.prompt, &self.reaction);
+    }
+}
+
+fn draw_prompt(window: &mut RenderWindow, resources: &Resources, prompt: &str, reaction: &str) {
+    let style = ui::style(resources);
+    let mut lines = wrap(prompt, 48);
+    if !reaction.is_empty() {
+        lines.insert(0, reaction.to_string());
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let colour = if i == 0 && !reaction.is_empty() { style.theme.highlight } else { style.theme.text };
+        style.draw_text(window, line, 20, colour, Vector2f::new(220.0, 280.0 + 24.0 * i as f32));
+    }
+}
+
+// breaks a text into lines of at most max_length characters, at the spaces between words
+fn wrap(text: &str, max_length: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > max_length {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+pub fn new(script: &str, context: &Context) -> Tutorial {
+    let (steps, errors) = parse(script);
+    for error in errors {
+        println!("Tutorial script, {}", error);
+    }
+    Tutorial {
+        steps,
+        step_index: 0,
+        is_new_step: true,
+        renderer: renderer::new(Vector2f::new(0.0, 600.0), 120, context.options, Rc::clone(&context.resources)),
+        board: board::new(vec![0; 8], 2, false, false),
+        origin: String::new(),
+        current_frame: 0,
+        is_continue: false,
+        typed: None,
+        prompt: String::new(),
+        reaction: String::new(),
+    }
+}
@@ -0,0 +1,71 @@
+use sfml::graphics::Color;
+
+use crate::config::Config;
+
+// All the colours the game uses.
+// The presets avoid telling things apart by red and green alone,
+// so colour blind players can still see, which bits are right.
+#[derive(Copy, Clone)]
+pub struct Theme {
+    pub background: Color,
+    pub text: Color,
+    pub correct: Color,
+    pub wrong: Color,
+    pub highlight: Color,
+    pub outline: Color,
+}
+
+// bluish green and vermillion from the Okabe-Ito palette
+pub fn default_theme() -> Theme {
+    Theme {
+        background: Color::WHITE,
+        text: Color::BLACK,
+        correct: Color::rgb(0, 158, 115),
+        wrong: Color::rgb(213, 94, 0),
+        highlight: Color::rgb(0, 114, 178),
+        outline: Color::rgb(220, 220, 220),
+    }
+}
+
+// blue and orange, which stay apart for every kind of colour blindness
+pub fn colorblind_theme() -> Theme {
+    Theme {
+        correct: Color::rgb(0, 114, 178),
+        wrong: Color::rgb(230, 159, 0),
+        highlight: Color::rgb(86, 180, 233),
+        ..default_theme()
+    }
+}
+
+// "theme" picks a preset, "theme.<colour>" overrides single colours with "r, g, b"
+pub fn from_config(config: &Config) -> Theme {
+    let mut theme = match config.get("theme") {
+        Some("colorblind") => colorblind_theme(),
+        _ => default_theme(),
+    };
+    let colours: [(&str, &mut Color); 6] = [
+        ("theme.background", &mut theme.background),
+        ("theme.text", &mut theme.text),
+        ("theme.correct", &mut theme.correct),
+        ("theme.wrong", &mut theme.wrong),
+        ("theme.highlight", &mut theme.highlight),
+        ("theme.outline", &mut theme.outline),
+    ];
+    for (key, colour) in colours {
+        if let Some(value) = config.get(key) {
+            match parse_colour(value) {
+                Some(parsed) => *colour = parsed,
+                None => println!("Could not read the colour {} = {}", key, value),
+            }
+        }
+    }
+    theme
+}
+
+fn parse_colour(text: &str) -> Option<Color> {
+    let parts: Vec<u8> = text.split(',').map(|part| part.trim().parse::<u8>()).collect::<Result<_, _>>().ok()?;
+    match parts[..] {
+        [r, g, b] => Some(Color::rgb(r, g, b)),
+        _ => None,
+    }
+}
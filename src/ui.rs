@@ -0,0 +1,62 @@
+use sfml::graphics::{Color, FloatRect, Font, RectangleShape, RenderTarget, RenderWindow, Shape, Text, Transformable};
+use sfml::system::Vector2f;
+
+use crate::resources::Resources;
+use crate::theme::Theme;
+
+pub mod form;
+pub mod layout;
+pub mod widget;
+
+// The font and colours every screen draws with, so nobody has to look them up on their own.
+#[derive(Copy, Clone)]
+pub struct Style<'a> {
+    pub font: &'a Font,
+    pub theme: Theme,
+}
+
+impl<'a> Style<'a> {
+    // text in the normal text colour
+    pub fn text(&self, string: &str, size: u32) -> Text<'a> {
+        self.coloured_text(string, size, self.theme.text)
+    }
+
+    pub fn coloured_text(&self, string: &str, size: u32, colour: Color) -> Text<'a> {
+        let mut text = Text::new(string, self.font, size);
+        text.set_fill_color(colour);
+        text
+    }
+
+    pub fn draw_text(&self, window: &mut RenderWindow, string: &str, size: u32, colour: Color, position: Vector2f) {
+        let mut text = self.coloured_text(string, size, colour);
+        text.set_position(position);
+        window.draw(&text);
+    }
+
+    // an empty box with an outline, the way buttons and slots are drawn
+    pub fn draw_frame(&self, window: &mut RenderWindow, bounds: FloatRect, outline: Color, thickness: f32) {
+        let mut frame = RectangleShape::with_size(Vector2f::new(bounds.width, bounds.height));
+        frame.set_position(Vector2f::new(bounds.left, bounds.top));
+        frame.set_fill_color(Color::TRANSPARENT);
+        frame.set_outline_color(outline);
+        frame.set_outline_thickness(thickness);
+        window.draw(&frame);
+    }
+
+    // a framed text, centred in its bounds
+    pub fn draw_button(&self, window: &mut RenderWindow, bounds: FloatRect, label: &str, is_focused: bool) {
+        let colour = if is_focused { self.theme.highlight } else { self.theme.text };
+        self.draw_frame(window, bounds, colour, 2.0);
+        let mut text = self.coloured_text(label, 22, colour);
+        let width = text.global_bounds().width;
+        text.set_position(Vector2f::new(bounds.left + (bounds.width - width) / 2.0, bounds.top + (bounds.height - 28.0) / 2.0));
+        window.draw(&text);
+    }
+}
+
+pub fn style(resources: &Resources) -> Style<'_> {
+    Style {
+        font: resources.font("default").unwrap(),
+        theme: *resources.theme(),
+    }
+}
@@ -0,0 +1,182 @@
+use sfml::graphics::{FloatRect, RenderWindow};
+use sfml::system::Vector2u;
+use sfml::window::mouse::Button;
+
+use crate::bindings::Action;
+use crate::game::game_tasks::GameTasks;
+use crate::ui::layout::{Anchor, Layout};
+use crate::ui::widget::{Kind, Widget};
+use crate::ui::Style;
+
+const MARGIN: f32 = 40.0;
+
+// what the player did with a form, widgets are named by their index
+pub enum UiEvent {
+    Nothing,
+    // a button got pressed, or an entry of a list picked
+    Activated(usize),
+    // a toggle, slider, number field or the selection of a list changed
+    Changed(usize),
+    Back,
+}
+
+/*
+A form owns some widgets, places them with its layout and keeps track of the focus.
+The focus moves with MenuUp/MenuDown (or the mouse), Submit (or A on a gamepad) presses
+the focused widget, CursorLeft/CursorRight change its value and Back (or B) leaves the form.
+ */
+pub struct Form {
+    widgets: Vec<Widget>,
+    layout: Layout,
+    anchor: Anchor,
+    focused: Option<usize>,
+}
+
+impl Form {
+    pub fn set_text(&mut self, index: usize, new_text: &str) {
+        match &mut self.widgets[index].kind {
+            Kind::Label { text, .. } | Kind::Button { text } | Kind::Toggle { text, .. }
+            | Kind::Slider { text, .. } | Kind::NumberField { text, .. } | Kind::TextField { text, .. } => *text = new_text.to_string(),
+            Kind::ListView { .. } => {}
+        }
+    }
+
+    pub fn is_on(&self, index: usize) -> bool {
+        matches!(self.widgets[index].kind, Kind::Toggle { is_on: true, .. })
+    }
+
+    pub fn value(&self, index: usize) -> f32 {
+        match self.widgets[index].kind {
+            Kind::Slider { value, .. } => value,
+            _ => 0.0,
+        }
+    }
+
+    pub fn number(&self, index: usize) -> i64 {
+        match self.widgets[index].kind {
+            Kind::NumberField { value, min, max, .. } => value.clamp(min, max),
+            _ => 0,
+        }
+    }
+
+    pub fn text(&self, index: usize) -> &str {
+        match &self.widgets[index].kind {
+            Kind::TextField { value, .. } => value,
+            _ => "",
+        }
+    }
+
+    pub fn selected(&self, index: usize) -> usize {
+        match self.widgets[index].kind {
+            Kind::ListView { selected, .. } => selected,
+            _ => 0,
+        }
+    }
+
+    pub fn set_items(&mut self, index: usize, new_items: Vec<String>) {
+        if let Kind::ListView { items, selected, .. } = &mut self.widgets[index].kind {
+            *selected = (*selected).min(new_items.len().saturating_sub(1));
+            *items = new_items;
+        }
+    }
+
+    fn bounds(&self, window_size: Vector2u) -> Vec<FloatRect> {
+        let sizes: Vec<_> = self.widgets.iter().map(|widget| widget.size).collect();
+        let mut bounds = vec![FloatRect::new(0.0, 0.0, 0.0, 0.0); self.widgets.len()];
+        let position = self.anchor.place(self.layout.size(&sizes), window_size, MARGIN);
+        self.layout.place(position, &sizes, &mut bounds);
+        bounds
+    }
+
+    fn move_focus(&mut self, direction: i32) {
+        let count = self.widgets.len() as i32;
+        let mut index = self.focused.map_or(if direction > 0 { -1 } else { count }, |focused| focused as i32);
+        for _ in 0..count {
+            index = (index + direction).rem_euclid(count);
+            if self.widgets[index as usize].is_focusable() {
+                self.focused = Some(index as usize);
+                return;
+            }
+        }
+    }
+
+    fn widget_at(&self, window_size: Vector2u, x: i32, y: i32) -> Option<(usize, FloatRect)> {
+        self.bounds(window_size).into_iter().enumerate()
+            .find(|(index, bounds)| self.widgets[*index].is_focusable() && bounds.contains2(x as f32, y as f32))
+    }
+
+    pub fn handle_task(&mut self, task: GameTasks, window_size: Vector2u) -> UiEvent {
+        match task {
+            GameTasks::Triggered(action) => {
+                let focused = self.focused;
+                match (action, focused) {
+                    (Action::MenuUp | Action::MenuDown, _) => {
+                        let direction = if action == Action::MenuUp { -1 } else { 1 };
+                        match focused {
+                            Some(index) if self.widgets[index].scroll(direction) => return UiEvent::Changed(index),
+                            _ => self.move_focus(direction),
+                        }
+                    }
+                    (Action::CursorLeft | Action::CursorRight, Some(index))
+                        if self.widgets[index].step(if action == Action::CursorLeft { -1 } else { 1 }) => return UiEvent::Changed(index),
+                    (Action::DeleteBackward, Some(index)) if self.widgets[index].delete_character() => return UiEvent::Changed(index),
+                    (Action::Submit | Action::InputOne, Some(index)) => {
+                        return match self.widgets[index].kind {
+                            Kind::Toggle { .. } => {
+                                self.widgets[index].step(1);
+                                UiEvent::Changed(index)
+                            }
+                            _ => UiEvent::Activated(index),
+                        };
+                    }
+                    (Action::Back | Action::InputZero, _) => return UiEvent::Back,
+                    _ => {}
+                }
+            }
+            GameTasks::TextEntered(character) => {
+                if let Some(index) = self.focused {
+                    if self.widgets[index].type_character(character) {
+                        return UiEvent::Changed(index);
+                    }
+                }
+            }
+            GameTasks::MouseMoved(x, y) => {
+                if let Some((index, _)) = self.widget_at(window_size, x, y) {
+                    self.focused = Some(index);
+                }
+            }
+            GameTasks::ClickPressed(Button::LEFT, x, y) => {
+                if let Some((index, bounds)) = self.widget_at(window_size, x, y) {
+                    self.focused = Some(index);
+                    return match self.widgets[index].kind {
+                        Kind::Button { .. } => UiEvent::Activated(index),
+                        Kind::ListView { selected, .. } => {
+                            // clicking the entry, that is already selected, picks it
+                            self.widgets[index].click(bounds, x as f32, y as f32);
+                            if self.selected(index) == selected { UiEvent::Activated(index) } else { UiEvent::Changed(index) }
+                        }
+                        _ => {
+                            self.widgets[index].click(bounds, x as f32, y as f32);
+                            UiEvent::Changed(index)
+                        }
+                    };
+                }
+            }
+            _ => {}
+        }
+        UiEvent::Nothing
+    }
+
+    pub fn render(&self, window: &mut RenderWindow, style: &Style, window_size: Vector2u) {
+        for (index, bounds) in self.bounds(window_size).into_iter().enumerate() {
+            self.widgets[index].draw(window, style, bounds, self.focused == Some(index));
+        }
+    }
+}
+
+// the first widget, that can take the focus, starts with it
+pub fn new(widgets: Vec<Widget>, layout: Layout, anchor: Anchor) -> Form {
+    let mut form = Form { widgets, layout, anchor, focused: None };
+    form.move_focus(1);
+    form
+}
@@ -0,0 +1,76 @@
+use sfml::graphics::FloatRect;
+use sfml::system::{Vector2f, Vector2u};
+
+// where a block of widgets sits in the window
+#[derive(Copy, Clone, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Center,
+}
+
+impl Anchor {
+    // the top left corner of a block with the given size, keeping margin to the window border
+    pub fn place(self, size: Vector2f, window_size: Vector2u, margin: f32) -> Vector2f {
+        match self {
+            Anchor::TopLeft => Vector2f::new(margin, margin),
+            Anchor::Center => Vector2f::new((window_size.x as f32 - size.x) / 2.0, (window_size.y as f32 - size.y) / 2.0),
+        }
+    }
+}
+
+// Widgets are referred to by their index, rows put them next to each other, columns below each other.
+pub enum Layout {
+    Widget(usize),
+    Row(Vec<Layout>),
+    Column(Vec<Layout>),
+}
+
+const SPACING: f32 = 10.0;
+
+impl Layout {
+    pub fn size(&self, sizes: &[Vector2f]) -> Vector2f {
+        match self {
+            Layout::Widget(index) => sizes[*index],
+            Layout::Row(children) => {
+                let children: Vec<Vector2f> = children.iter().map(|child| child.size(sizes)).collect();
+                Vector2f::new(
+                    children.iter().map(|size| size.x).sum::<f32>() + SPACING * children.len().saturating_sub(1) as f32,
+                    children.iter().map(|size| size.y).fold(0.0, f32::max),
+                )
+            }
+            Layout::Column(children) => {
+                let children: Vec<Vector2f> = children.iter().map(|child| child.size(sizes)).collect();
+                Vector2f::new(
+                    children.iter().map(|size| size.x).fold(0.0, f32::max),
+                    children.iter().map(|size| size.y).sum::<f32>() + SPACING * children.len().saturating_sub(1) as f32,
+                )
+            }
+        }
+    }
+
+    // writes the bounds of every widget in the layout into bounds, starting at position
+    pub fn place(&self, position: Vector2f, sizes: &[Vector2f], bounds: &mut [FloatRect]) {
+        match self {
+            Layout::Widget(index) => bounds[*index] = FloatRect::new(position.x, position.y, sizes[*index].x, sizes[*index].y),
+            Layout::Row(children) => {
+                let mut x = position.x;
+                for child in children {
+                    child.place(Vector2f::new(x, position.y), sizes, bounds);
+                    x += child.size(sizes).x + SPACING;
+                }
+            }
+            Layout::Column(children) => {
+                let mut y = position.y;
+                for child in children {
+                    child.place(Vector2f::new(position.x, y), sizes, bounds);
+                    y += child.size(sizes).y + SPACING;
+                }
+            }
+        }
+    }
+}
+
+// the usual layout of a menu: every widget below the one before
+pub fn column_of(count: usize) -> Layout {
+    Layout::Column((0..count).map(Layout::Widget).collect())
+}
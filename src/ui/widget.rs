@@ -0,0 +1,216 @@
+use sfml::graphics::{FloatRect, RectangleShape, RenderTarget, RenderWindow, Shape, Transformable};
+use sfml::system::Vector2f;
+
+use crate::ui::Style;
+
+const WIDTH: f32 = 400.0;
+const HEIGHT: f32 = 44.0;
+const LIST_ROW_HEIGHT: f32 = 28.0;
+
+pub enum Kind {
+    Label { text: String, text_size: u32 },
+    Button { text: String },
+    Toggle { text: String, is_on: bool },
+    Slider { text: String, value: f32, min: f32, max: f32, step: f32 },
+    // typing digits replaces the value, left and right count up and down
+    NumberField { text: String, value: i64, min: i64, max: i64 },
+    TextField { text: String, value: String, max_length: usize },
+    // shows rows entries at once and scrolls with the selection
+    ListView { items: Vec<String>, selected: usize, scroll: usize, rows: usize },
+}
+
+pub struct Widget {
+    pub kind: Kind,
+    pub size: Vector2f,
+}
+
+impl Widget {
+    pub fn with_width(mut self, width: f32) -> Widget {
+        self.size.x = width;
+        self
+    }
+
+    pub fn is_focusable(&self) -> bool {
+        !matches!(self.kind, Kind::Label { .. })
+    }
+
+    // left and right, returns true, if the value changed
+    pub fn step(&mut self, direction: i32) -> bool {
+        match &mut self.kind {
+            Kind::Toggle { is_on, .. } => {
+                *is_on = !*is_on;
+                true
+            }
+            Kind::Slider { value, min, max, step, .. } => {
+                let old = *value;
+                *value = (*value + *step * direction as f32).clamp(*min, *max);
+                *value != old
+            }
+            Kind::NumberField { value, min, max, .. } => {
+                let old = *value;
+                *value = (*value + direction as i64).clamp(*min, *max);
+                *value != old
+            }
+            _ => false,
+        }
+    }
+
+    // up and down inside a list, returns false at its ends, so the focus can move on
+    pub fn scroll(&mut self, direction: i32) -> bool {
+        match &mut self.kind {
+            Kind::ListView { items, selected, scroll, rows } => {
+                let next = *selected as i64 + direction as i64;
+                if next < 0 || next >= items.len() as i64 {
+                    return false;
+                }
+                *selected = next as usize;
+                if *selected < *scroll {
+                    *scroll = *selected;
+                } else if *selected >= *scroll + *rows {
+                    *scroll = *selected + 1 - *rows;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // a digit typed into a number field gets appended, as long as the value stays in range
+    pub fn type_character(&mut self, character: char) -> bool {
+        match &mut self.kind {
+            Kind::NumberField { value, max, .. } => match character.to_digit(10) {
+                Some(digit) => {
+                    let typed = *value * 10 + digit as i64;
+                    *value = if typed <= *max { typed } else { digit as i64 };
+                    true
+                }
+                None => false,
+            },
+            Kind::TextField { value, max_length, .. } if value.chars().count() < *max_length => {
+                value.push(character);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn delete_character(&mut self) -> bool {
+        match &mut self.kind {
+            Kind::NumberField { value, .. } => {
+                *value /= 10;
+                true
+            }
+            Kind::TextField { value, .. } => value.pop().is_some(),
+            _ => false,
+        }
+    }
+
+    // a click at x, y inside the bounds, returns true, if the value changed
+    pub fn click(&mut self, bounds: FloatRect, x: f32, y: f32) -> bool {
+        match &mut self.kind {
+            Kind::Toggle { is_on, .. } => {
+                *is_on = !*is_on;
+                true
+            }
+            Kind::Slider { value, min, max, step, .. } => {
+                let track = slider_track(bounds);
+                let fraction = ((x - track.left) / track.width).clamp(0.0, 1.0);
+                *value = (*min + ((*max - *min) * fraction / *step).round() * *step).clamp(*min, *max);
+                true
+            }
+            Kind::ListView { items, selected, scroll, .. } => {
+                let row = *scroll + ((y - bounds.top) / LIST_ROW_HEIGHT) as usize;
+                if row < items.len() {
+                    *selected = row;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn draw(&self, window: &mut RenderWindow, style: &Style, bounds: FloatRect, is_focused: bool) {
+        let theme = style.theme;
+        let colour = if is_focused { theme.highlight } else { theme.text };
+        let text_position = Vector2f::new(bounds.left + 12.0, bounds.top + 6.0);
+        match &self.kind {
+            Kind::Label { text, text_size } => style.draw_text(window, text, *text_size, theme.text, Vector2f::new(bounds.left, bounds.top)),
+            Kind::Button { text } => style.draw_button(window, bounds, text, is_focused),
+            Kind::Toggle { text, is_on } => {
+                style.draw_frame(window, bounds, if is_focused { theme.highlight } else { theme.outline }, 2.0);
+                style.draw_text(window, &format!("{}: {}", text, if *is_on { "on" } else { "off" }), 24, colour, text_position);
+            }
+            Kind::Slider { text, value, min, max, .. } => {
+                style.draw_frame(window, bounds, if is_focused { theme.highlight } else { theme.outline }, 2.0);
+                style.draw_text(window, &format!("{}: {}", text, value), 24, colour, text_position);
+                let track = slider_track(bounds);
+                style.draw_frame(window, track, theme.outline, 1.0);
+                let mut knob = RectangleShape::with_size(Vector2f::new(8.0, track.height + 8.0));
+                let fraction = if max > min { (value - min) / (max - min) } else { 0.0 };
+                knob.set_position(Vector2f::new(track.left + track.width * fraction - 4.0, track.top - 4.0));
+                knob.set_fill_color(colour);
+                window.draw(&knob);
+            }
+            Kind::NumberField { text, value, .. } => {
+                style.draw_frame(window, bounds, if is_focused { theme.highlight } else { theme.outline }, 2.0);
+                style.draw_text(window, &format!("{}: < {} >", text, value), 24, colour, text_position);
+            }
+            Kind::TextField { text, value, .. } => {
+                style.draw_frame(window, bounds, if is_focused { theme.highlight } else { theme.outline }, 2.0);
+                let cursor = if is_focused { "_" } else { "" };
+                style.draw_text(window, &format!("{}: {}{}", text, value, cursor), 24, colour, text_position);
+            }
+            Kind::ListView { items, selected, scroll, rows } => {
+                style.draw_frame(window, bounds, if is_focused { theme.highlight } else { theme.outline }, 2.0);
+                for (row, item) in items.iter().enumerate().skip(*scroll).take(*rows) {
+                    let is_selected = row == *selected;
+                    let line = if is_selected { format!("> {}", item) } else { item.clone() };
+                    let position = Vector2f::new(bounds.left + 8.0, bounds.top + 2.0 + LIST_ROW_HEIGHT * (row - scroll) as f32);
+                    style.draw_text(window, &line, 20, if is_selected { colour } else { theme.text }, position);
+                }
+            }
+        }
+    }
+}
+
+// the bar a slider's knob moves on, in the right half of the widget
+fn slider_track(bounds: FloatRect) -> FloatRect {
+    FloatRect::new(bounds.left + bounds.width / 2.0, bounds.top + bounds.height / 2.0 - 2.0, bounds.width / 2.0 - 16.0, 4.0)
+}
+
+pub fn label(text: &str, text_size: u32) -> Widget {
+    Widget {
+        kind: Kind::Label { text: text.to_string(), text_size },
+        size: Vector2f::new(WIDTH, text_size as f32 * 1.3 * text.lines().count().max(1) as f32),
+    }
+}
+
+pub fn button(text: &str) -> Widget {
+    Widget { kind: Kind::Button { text: text.to_string() }, size: Vector2f::new(WIDTH, HEIGHT) }
+}
+
+pub fn toggle(text: &str, is_on: bool) -> Widget {
+    Widget { kind: Kind::Toggle { text: text.to_string(), is_on }, size: Vector2f::new(WIDTH, HEIGHT) }
+}
+
+pub fn slider(text: &str, value: f32, min: f32, max: f32, step: f32) -> Widget {
+    Widget { kind: Kind::Slider { text: text.to_string(), value, min, max, step }, size: Vector2f::new(WIDTH, HEIGHT) }
+}
+
+pub fn number_field(text: &str, value: i64, min: i64, max: i64) -> Widget {
+    Widget { kind: Kind::NumberField { text: text.to_string(), value, min, max }, size: Vector2f::new(WIDTH, HEIGHT) }
+}
+
+pub fn text_field(text: &str, value: &str, max_length: usize) -> Widget {
+    Widget {
+        kind: Kind::TextField { text: text.to_string(), value: value.to_string(), max_length },
+        size: Vector2f::new(WIDTH, HEIGHT),
+    }
+}
+
+pub fn list_view(items: Vec<String>, rows: usize) -> Widget {
+    Widget {
+        kind: Kind::ListView { items, selected: 0, scroll: 0, rows },
+        size: Vector2f::new(WIDTH, LIST_ROW_HEIGHT * rows as f32 + 4.0),
+    }
+}
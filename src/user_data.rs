@@ -0,0 +1,38 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Where the game keeps what belongs to the player (high scores and such), following the
+// conventions of every system: XDG on Linux, Application Support on macOS and AppData on Windows.
+// Falls back to the working directory, if none of them can be found.
+pub fn dir() -> PathBuf {
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+    };
+    match base {
+        Some(base) => base.join("mr_binaer"),
+        None => PathBuf::from("."),
+    }
+}
+
+pub fn path(file_name: &str) -> PathBuf {
+    dir().join(file_name)
+}
+
+// Writes into a temporary file next to the real one and renames it afterwards,
+// so a crash while writing never leaves a half written file behind.
+pub fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, content)?;
+    fs::rename(&temporary, path)
+}